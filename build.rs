@@ -0,0 +1,87 @@
+/// Generates the solution registry of the active year, which is included by the `registry` binary.
+/// Every solution binary `src/bin/{day}.rs` or `src/bin/{day}_{variant}.rs` is included as a module and registered
+/// with the `PARTS` and `run_parts` items generated by the `solution!` macro.
+use std::{env, fmt::Write, fs, path::Path};
+
+include!("src/template/event_days.rs");
+
+/// Parses the name of a solution binary of the active year into its day and variant.
+fn parse_stem(stem: &str) -> Option<(u8, Option<&str>)> {
+    let (day, variant) = match stem.split_once('_') {
+        Some((day, variant)) => (day, Some(variant)),
+        None => (stem, None),
+    };
+
+    if day.len() != 2 || !day.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    if variant
+        .is_some_and(|v| v.is_empty() || !v.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_'))
+    {
+        return None;
+    }
+
+    let day: u8 = day.parse().ok()?;
+    (1..=MAX_DAYS).contains(&day).then_some((day, variant))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=src/template/event_days.rs");
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut solutions: Vec<(u8, Option<String>, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .map_while(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+
+                    let (day, variant) = parse_stem(path.file_stem()?.to_str()?)?;
+                    Some((day, variant.map(String::from), path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    solutions.sort();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (day, variant, path) in &solutions {
+        let module = match variant {
+            Some(variant) => format!("solution_{day:02}_{variant}"),
+            None => format!("solution_{day:02}"),
+        };
+
+        writeln!(
+            modules,
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {path:?}]\nmod {module};"
+        )
+        .unwrap();
+
+        writeln!(
+            entries,
+            "    Entry {{ day: advent_of_code::day!({day}), variant: {variant:?}, parts: {module}::PARTS, solve: {module}::run_parts }},"
+        )
+        .unwrap();
+    }
+
+    // NOTE: solutions are not compiled into the tests of the registry, their tests run with their own binaries.
+    let registry = format!(
+        "use advent_of_code::template::registry::Entry;\n\n{modules}\n\
+         #[cfg(not(test))]\npub const SOLUTIONS: &[Entry] = &[\n{entries}];\n\n\
+         #[cfg(test)]\npub const SOLUTIONS: &[Entry] = &[];\n"
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
@@ -97,7 +97,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         })
         .collect();
 
-    let valids = vec![
+    let valids = [
         Array2::from_shape_vec((3, 3), vec!['M', '.', 'M', '.', 'A', '.', 'S', '.', 'S']).unwrap(),
         Array2::from_shape_vec((3, 3), vec!['S', '.', 'S', '.', 'A', '.', 'M', '.', 'M']).unwrap(),
         Array2::from_shape_vec((3, 3), vec!['M', '.', 'S', '.', 'A', '.', 'M', '.', 'S']).unwrap(),
@@ -123,7 +123,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             sub_mut[[1, 2]] = '.';
             sub_mut[[2, 1]] = '.';
 
-            if valids.iter().any(|v| v == sub_mut) {
+            if valids.contains(&sub_mut) {
                 Some((r, c))
             } else {
                 None
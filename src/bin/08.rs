@@ -32,8 +32,8 @@ fn is_in_bouds(pos: IVec2, map_width: usize, map_heigth: usize) -> bool {
 pub fn part_one(input: &str) -> Option<u32> {
     let (map_width, map_heigth, antennas) = parse_input(input);
     let num_antinodes = antennas
-        .iter()
-        .flat_map(|(_, positions)| {
+        .values()
+        .flat_map(|positions| {
             positions.iter().combinations(2).flat_map(|pair| {
                 let (a, b) = (pair[0], pair[1]);
                 let delta = b - a;
@@ -49,8 +49,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let (map_width, map_heigth, antennas) = parse_input(input);
     let num_antinodes = antennas
-        .iter()
-        .flat_map(|(_, positions)| {
+        .values()
+        .flat_map(|positions| {
             positions.iter().combinations(2).flat_map(|pair| {
                 let (a, b) = (pair[0], pair[1]);
                 let delta = b - a;
@@ -17,7 +17,7 @@ fn blink(n: u64, blinks: u8) -> u64 {
         blink(1, blinks - 1)
     } else {
         let num_digits = n.ilog10() + 1;
-        if num_digits % 2 == 0 {
+        if num_digits.is_multiple_of(2) {
             let split_at = num_digits / 2;
             let first_half = n / 10u64.pow(split_at);
             let second_half = n % 10u64.pow(split_at);
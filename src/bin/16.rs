@@ -113,33 +113,28 @@ impl Pos {
     }
 }
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, parse = parse_input);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let grid = Grid::<char>::from_input(input);
-
-    let start_pos = grid
-        .indexed_iter()
-        .find(|(_, c)| **c == 'S')
-        .map(|((y, x), _)| IVec2::new(x as i32, y as i32))
-        .unwrap();
-
-    let finish_pos = grid
-        .indexed_iter()
-        .find(|(_, c)| **c == 'E')
-        .map(|((y, x), _)| IVec2::new(x as i32, y as i32))
-        .unwrap();
-
-    let start = Pos {
-        pos: start_pos,
-        dir: Direction::Right,
-    };
+pub struct Maze {
+    grid: Grid<char>,
+    start: Pos,
+    finish_pos: IVec2,
+}
 
-    let result = dijkstra(&start, |p| p.successors(&grid), |p| p.pos == finish_pos);
-    Some(result.unwrap().1 as u32)
+impl Maze {
+    /// Cost of the cheapest path from the start to every reachable state.
+    fn dist_from_start(&self) -> HashMap<Pos, usize> {
+        let mut dist_map: HashMap<Pos, usize> =
+            dijkstra_all(&self.start, |p| p.successors(&self.grid))
+                .into_iter()
+                .map(|(p, (_, cost))| (p, cost))
+                .collect();
+        dist_map.insert(self.start, 0);
+        dist_map
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn parse_input(input: &str) -> Maze {
     let grid = Grid::<char>::from_input(input);
 
     let start_pos = grid
@@ -159,16 +154,29 @@ pub fn part_two(input: &str) -> Option<u32> {
         dir: Direction::Right,
     };
 
-    let dist_from_start_map = {
-        let mut dist_map = dijkstra_all(&start, |p| p.successors(&grid));
-        dist_map.insert(start, (start, 0));
-        dist_map
-    };
+    Maze {
+        grid,
+        start,
+        finish_pos,
+    }
+}
+
+pub fn part_one(maze: &Maze) -> Option<u32> {
+    let result = dijkstra(
+        &maze.start,
+        |p| p.successors(&maze.grid),
+        |p| p.pos == maze.finish_pos,
+    );
+    result.map(|(_, cost)| cost as u32)
+}
 
-    let shortest_finish_dist = dist_from_start_map
+pub fn part_two(maze: &Maze) -> Option<u32> {
+    let dist_from_start = maze.dist_from_start();
+
+    let shortest_finish_dist = dist_from_start
         .iter()
-        .filter_map(|(p, &(_, cost))| {
-            if p.pos == finish_pos {
+        .filter_map(|(p, &cost)| {
+            if p.pos == maze.finish_pos {
                 Some(cost)
             } else {
                 None
@@ -178,11 +186,11 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     let mut dist_from_finish: HashMap<Pos, usize> = HashMap::new();
 
-    dist_from_start_map
+    dist_from_start
         .iter()
-        .filter(|(p, &(_, cost))| p.pos == finish_pos && cost == shortest_finish_dist)
+        .filter(|(p, &cost)| p.pos == maze.finish_pos && cost == shortest_finish_dist)
         .map(|(&finish_state, _)| {
-            let mut dist_map = dijkstra_all(&finish_state, |p| p.predecessors(&grid));
+            let mut dist_map = dijkstra_all(&finish_state, |p| p.predecessors(&maze.grid));
             dist_map.insert(finish_state, (finish_state, 0));
             dist_map
         })
@@ -195,11 +203,6 @@ pub fn part_two(input: &str) -> Option<u32> {
             }
         });
 
-    let dist_from_start: HashMap<Pos, usize> = dist_from_start_map
-        .iter()
-        .map(|(p, &(_, cost))| (*p, cost))
-        .collect();
-
     let tiles_on_path = dist_from_start
         .iter()
         .filter(|&(p, &cost_start)| {
@@ -224,7 +227,7 @@ mod tests {
     #[case(&advent_of_code::template::read_file_part("examples", DAY, 2), Some(11048))]
     fn test_part_one(#[case] input: &str, #[case] expected: Option<u32>) {
         tracing_init(Level::INFO);
-        let result = part_one(input);
+        let result = part_one(&parse_input(input));
         assert_eq!(result, expected);
     }
 
@@ -233,7 +236,7 @@ mod tests {
     #[case(&advent_of_code::template::read_file_part("examples", DAY, 2), Some(64))]
     fn test_part_two(#[case] input: &str, #[case] expected: Option<u32>) {
         tracing_init(Level::INFO);
-        let result = part_two(input);
+        let result = part_two(&parse_input(input));
         assert_eq!(result, expected);
     }
 }
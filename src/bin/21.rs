@@ -252,16 +252,16 @@ mod tests {
     fn test_keyberpad_split() {
         let a = translate_controls(
             PadType::Keypad,
-            &mut "<^>vA".chars().collect::<Vec<_>>().as_ref(),
+            "<^>vA".chars().collect::<Vec<_>>().as_ref(),
         );
         let b = translate_controls(
             PadType::Keypad,
-            &mut "<^>vA".chars().collect::<Vec<_>>().as_ref(),
+            "<^>vA".chars().collect::<Vec<_>>().as_ref(),
         );
-        let ab = a.into_iter().chain(b.into_iter()).collect::<Vec<_>>();
+        let ab = a.into_iter().chain(b).collect::<Vec<_>>();
         let check_equal = translate_controls(
             PadType::Keypad,
-            &mut "<^>vA<^>vA".chars().collect::<Vec<_>>().as_ref(),
+            "<^>vA<^>vA".chars().collect::<Vec<_>>().as_ref(),
         );
         assert_eq!(ab, check_equal)
     }
@@ -27,7 +27,7 @@ pub trait AocGrid<T> {
 
     fn get_ivec(&self, pos: IVec2) -> Option<&T>;
 
-    fn neighbors<'a>(&'a self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)>
+    fn neighbors<'a>(&'a self, pos: IVec2) -> impl Iterator<Item = (IVec2, &'a T)>
     where
         T: 'a;
 }
@@ -52,7 +52,7 @@ where
         Grid::from_vec(v, width)
     }
 
-    fn neighbors<'a>(&'a self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)>
+    fn neighbors<'a>(&'a self, pos: IVec2) -> impl Iterator<Item = (IVec2, &'a T)>
     where
         T: 'a,
     {
@@ -76,7 +76,7 @@ impl AocGrid<char> for Grid<char> {
         Grid::from_vec(v, width)
     }
 
-    fn neighbors<'a>(&'a self, pos: IVec2) -> impl Iterator<Item = (IVec2, &char)>
+    fn neighbors<'a>(&'a self, pos: IVec2) -> impl Iterator<Item = (IVec2, &'a char)>
     where
         char: 'a,
     {
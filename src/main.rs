@@ -1,14 +1,23 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, archive, download, examples, read, scaffold, solve, time, verify, watch_day,
+};
+use advent_of_code::template::{registry::Runner, year};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{calendar::Calendar, Day};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        budget::{self, Budget},
+        calendar::Calendar,
+        commands::time,
+        export::Format,
+        year, Day,
+    };
+    use std::{path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
@@ -16,25 +25,63 @@ mod args {
         },
         Read {
             day: Day,
+            part: Option<u8>,
+            refresh: bool,
+        },
+        Examples {
+            day: Day,
+            yes: bool,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
+            variant: Option<String>,
             release: bool,
             dhat: bool,
+            metrics: bool,
+            input: Option<String>,
             submit: Option<u8>,
+            yes: bool,
         },
         All {
             release: bool,
+            metrics: bool,
+            budget: Budget,
+            jobs: Option<usize>,
+            in_process: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            metrics: bool,
+            budget: Budget,
+            in_process: bool,
+        },
+        History {
+            day: Day,
+        },
+        Export {
+            format: Format,
+            out: Option<PathBuf>,
+        },
+        Verify {
+            day: Option<Day>,
+            accept: bool,
+        },
+        WatchDay {
+            day: Day,
+            variant: Option<String>,
+            release: bool,
+        },
+        Archive {
+            next: Option<u16>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -43,36 +90,95 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // every command works on the active year, unless another one is selected.
+        if let Some(year) = args.opt_value_from_str("--year")? {
+            year::select(year);
+        }
+
+        // only days of the event of the selected year are accepted.
+        let calendar = Calendar::current();
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                metrics: args.contains("--metrics"),
+                budget: parse_budget(&mut args)?,
+                jobs: args.opt_value_from_str("--jobs")?,
+                in_process: args.contains("--in-process"),
             },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
-
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
+            Some("time") if args.contains("--history") => AppArguments::History {
+                day: calendar.check(args.free_from_str()?)?,
+            },
+            Some("time") => match args.opt_value_from_str("--export")? {
+                Some(format) => AppArguments::Export {
+                    format,
+                    out: args.opt_value_from_str("--out")?,
+                },
+                None => {
+                    let all = args.contains("--all");
+                    let store = args.contains("--store");
+                    let compare = args.contains("--compare");
+                    let threshold = args.opt_value_from_str("--threshold")?;
+                    let metrics = args.contains("--metrics");
+                    let in_process = args.contains("--in-process");
+                    let budget = parse_budget(&mut args)?;
+
+                    AppArguments::Time {
+                        all,
+                        day: parse_day(&calendar, args.opt_free_from_str()?)?,
+                        store,
+                        compare: compare.then(|| threshold.unwrap_or(time::DEFAULT_THRESHOLD)),
+                        metrics,
+                        budget,
+                        in_process,
+                    }
+                }
+            },
+            Some("verify") => {
+                let accept = args.contains("--accept");
+
+                AppArguments::Verify {
+                    day: parse_day(&calendar, args.opt_free_from_str()?)?,
+                    accept,
                 }
             }
+            Some("watch-day") => AppArguments::WatchDay {
+                day: calendar.check(args.free_from_str()?)?,
+                variant: args.opt_value_from_str("--variant")?,
+                release: args.contains("--release"),
+            },
+            Some("archive") => AppArguments::Archive {
+                next: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                day: calendar.check(args.free_from_str()?)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                day: calendar.check(args.free_from_str()?)?,
+                part: args.opt_value_from_str("--part")?,
+                refresh: args.contains("--refresh"),
+            },
+            Some("examples") => AppArguments::Examples {
+                day: calendar.check(args.free_from_str()?)?,
+                yes: args.contains("--yes"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                day: calendar.check(args.free_from_str()?)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                day: calendar.check(args.free_from_str()?)?,
+                variant: args.opt_value_from_str("--variant")?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                metrics: args.contains("--metrics"),
+                input: args.opt_value_from_str("--input")?,
+                yes: args.contains("--yes"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,47 +199,108 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_day(calendar: &Calendar, day: Option<Day>) -> Result<Option<Day>, String> {
+        day.map(|day| calendar.check(day)).transpose()
+    }
+
+    /// Parses `--timeout <seconds>` and `--memory <MB>`, which limit each part of a solution.
+    /// The memory limit is best-effort, see the `budget` module.
+    fn parse_budget(args: &mut pico_args::Arguments) -> Result<Budget, pico_args::Error> {
+        Ok(Budget {
+            timeout: args.opt_value_from_fn("--timeout", budget::parse_timeout)?,
+            memory_mb: args.opt_value_from_str("--memory")?,
+        })
+    }
 }
 
 fn main() {
+    year::migrate_legacy_data();
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                metrics,
+                budget,
+                jobs,
+                in_process,
+            } => {
+                let runner = if in_process {
+                    Runner::Registry
+                } else {
+                    Runner::processes(jobs)
+                };
+                all::handle(release, metrics, budget, runner);
+            }
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                compare,
+                metrics,
+                budget,
+                in_process,
+            } => {
+                // NOTE: benchmarks always run one after another.
+                let runner = if in_process {
+                    Runner::Registry
+                } else {
+                    Runner::Processes { jobs: 1 }
+                };
+                time::handle(day, all, store, compare, metrics, budget, runner);
+            }
+            AppArguments::History { day } => time::show_history(day),
+            AppArguments::Export { format, out } => time::export(format, out),
+            AppArguments::Verify { day, accept } => verify::handle(day, accept),
+            AppArguments::WatchDay {
+                day,
+                variant,
+                release,
+            } => watch_day::handle(day, variant, release),
+            AppArguments::Archive { next } => archive::handle(next),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Read { day, part, refresh } => read::handle(day, part, refresh),
+            AppArguments::Examples { day, yes } => examples::handle(day, yes),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // NOTE: the download comes first so that the template can use the title of the puzzle.
                 if download {
                     download::handle(day);
                 }
+                scaffold::handle(day, overwrite, template.as_deref());
             }
             AppArguments::Solve {
                 day,
+                variant,
                 release,
                 dhat,
+                metrics,
+                input,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                yes,
+            } => solve::handle(day, variant, release, dhat, metrics, input, submit, yes),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
                         download::handle(day);
-                        read::handle(day)
+                        scaffold::handle(day, false, None);
+                        read::handle(day, None, false)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run on the days of the {} event. \
+                            Please use `scaffold` with a specific day.",
+                            Calendar::current().year
                         );
                         process::exit(1)
                     }
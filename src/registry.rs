@@ -0,0 +1,7 @@
+// Runs all days of the active year in-process, see `template::registry`.
+// The registered solutions are generated by the build script from `src/bin`.
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+fn main() {
+    advent_of_code::template::registry::main(SOLUTIONS);
+}
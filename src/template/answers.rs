@@ -0,0 +1,146 @@
+/// Store of known-correct answers, kept as `data/{year}/answers/{day}-{part}.txt`.
+/// Answers are recorded on a correct submission or with `cargo verify --accept`, `all` / `time` / `verify`
+/// check the output of each part against them.
+use std::{
+    env, fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+use crate::template::{
+    report::{Report, Status},
+    year, Day,
+};
+
+/// Outcome of checking a part's output against the known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// The part returned the known answer.
+    Pass,
+    /// The part returned a different answer.
+    Changed { expected: String },
+    /// The part did not return an answer, but one is known.
+    Fail,
+    /// No answer is known for the part.
+    Unknown,
+}
+
+impl Check {
+    /// Returns `true` if the part regressed.
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self, Check::Changed { .. } | Check::Fail)
+    }
+
+    /// Formats the check for the summary of a day, e.g. `Part 1 ✔` or `Part 2 ✖ changed (expected 42)`.
+    pub fn format(&self, part: u8) -> String {
+        match self {
+            Check::Pass => format!("Part {part} ✔"),
+            Check::Changed { expected } if !expected.contains('\n') => {
+                format!("Part {part} ✖ changed (expected {expected})")
+            }
+            Check::Changed { .. } => format!("Part {part} ✖ changed"),
+            Check::Fail => format!("Part {part} ✖ failed"),
+            Check::Unknown => format!("Part {part} ? unknown"),
+        }
+    }
+}
+
+fn answer_path(day: Day, part: u8) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join(year::data_dir())
+        .join("answers")
+        .join(format!("{day}-{part}.txt"))
+}
+
+// NOTE: editors tend to add a trailing newline when answers are edited by hand.
+fn normalize(answer: &str) -> &str {
+    answer.trim_end_matches(['\n', '\r'])
+}
+
+/// Reads the known answer of a part, if any.
+pub fn read(day: Day, part: u8) -> io::Result<Option<String>> {
+    match fs::read_to_string(answer_path(day, part)) {
+        Ok(answer) => Ok(Some(normalize(&answer).to_string())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Records `answer` as the known answer of a part, replacing a previous one.
+pub fn store(day: Day, part: u8, answer: &str) -> io::Result<()> {
+    let path = answer_path(day, part);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, normalize(answer))
+}
+
+/// Compares the output of a part with its known answer.
+pub fn check(known: Option<&str>, answer: Option<&str>) -> Check {
+    match (known, answer) {
+        (None, _) => Check::Unknown,
+        (Some(_), None) => Check::Fail,
+        (Some(known), Some(answer)) if normalize(known) == normalize(answer) => Check::Pass,
+        (Some(known), Some(_)) => Check::Changed {
+            expected: known.to_string(),
+        },
+    }
+}
+
+/// Checks the part reports of a solution run against the store. Returns the part and its check.
+pub fn check_reports(reports: &[Report]) -> Vec<(u8, Check)> {
+    reports
+        .iter()
+        .filter_map(|r| {
+            let part = part_of(r)?;
+            let known = read(r.day, part).unwrap_or_else(|e| {
+                eprintln!(
+                    "Failed to read known answer of day {} part {part}: {e}",
+                    r.day
+                );
+                None
+            });
+            let answer = r.answer.as_deref().filter(|_| r.status == Status::Solved);
+            Some((part, check(known.as_deref(), answer)))
+        })
+        .collect()
+}
+
+/// The part number of a part report, `None` for other stages.
+pub fn part_of(report: &Report) -> Option<u8> {
+    (1..=2).find(|part| report.stage == Report::part_stage(*part))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Check};
+
+    #[test]
+    fn passes_known_answers() {
+        assert_eq!(check(Some("42"), Some("42")), Check::Pass);
+        assert_eq!(check(Some("#.\n.#"), Some("#.\n.#\n")), Check::Pass);
+    }
+
+    #[test]
+    fn detects_changed_answers() {
+        let res = check(Some("42"), Some("43"));
+        assert_eq!(
+            res,
+            Check::Changed {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(res.is_mismatch(), true);
+        assert_eq!(res.format(1), "Part 1 ✖ changed (expected 42)");
+    }
+
+    #[test]
+    fn fails_missing_answers() {
+        assert_eq!(check(Some("42"), None), Check::Fail);
+        assert_eq!(check(None, None), Check::Unknown);
+        assert_eq!(check(None, Some("1")).is_mismatch(), false);
+    }
+}
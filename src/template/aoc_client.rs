@@ -0,0 +1,472 @@
+/// Client for the Advent of Code website.
+/// Authenticates with the session cookie of a logged-in browser, which is read from the `AOC_SESSION` env var or
+/// from `~/.adventofcode.session` (the file used by `aoc-cli`). The base URL can be overridden with `AOC_BASE_URL`.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{submissions::Verdict, year, Day};
+
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const SESSION_FILE: &str = ".adventofcode.session";
+const USER_AGENT: &str = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in the environment or the session file.
+    MissingSession,
+    /// No year is selected, see [`year::try_current`].
+    MissingYear,
+    /// The site responded with a non-success status, e.g. 404 for a puzzle that is not unlocked yet.
+    Http {
+        status: u16,
+        body: String,
+    },
+    /// The request could not be sent or the response could not be read.
+    Transport(String),
+    /// The response did not have the expected shape.
+    UnexpectedResponse(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or write it to ~/{SESSION_FILE}."
+            ),
+            AocClientError::MissingYear => {
+                write!(f, "no year configured, set AOC_YEAR or pass --year.")
+            }
+            AocClientError::Http { status, .. } => match status {
+                400 | 500 => write!(
+                    f,
+                    "request failed with status {status}, is the session cookie still valid?"
+                ),
+                404 => write!(
+                    f,
+                    "request failed with status 404, is the puzzle unlocked yet?"
+                ),
+                _ => write!(f, "request failed with status {status}."),
+            },
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::UnexpectedResponse(e) => write!(f, "unexpected response: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => AocClientError::Http {
+                status,
+                body: response.into_string().unwrap_or_default(),
+            },
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub base_url: String,
+    pub session: String,
+    pub year: u16,
+}
+
+impl Config {
+    /// Reads the configuration from the environment and the session file.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = env::var(SESSION_ENV)
+            .ok()
+            .or_else(read_session_file)
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .ok_or(AocClientError::MissingSession)?;
+
+        let year = year::try_current().ok_or(AocClientError::MissingYear)?;
+
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Config {
+            base_url,
+            session,
+            year,
+        })
+    }
+}
+
+fn read_session_file() -> Option<String> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(&home).join(".config"));
+
+    [
+        PathBuf::from(&home).join(SESSION_FILE),
+        config_dir.join("adventofcode.session"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(path).ok())
+}
+
+pub struct Client {
+    config: Config,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(TIMEOUT)
+            .user_agent(USER_AGENT)
+            .build();
+
+        Client { config, agent }
+    }
+
+    /// Creates a client configured by the environment, see [`Config::from_env`].
+    pub fn from_env() -> Result<Self, AocClientError> {
+        Config::from_env().map(Client::new)
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.config.base_url.trim_end_matches('/'),
+            self.config.year,
+            day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.config.session)
+    }
+
+    /// Fetches the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}/input", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the description of a day as markdown. Includes part two once part one is solved.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(day))
+            .set("Cookie", &self.cookie())
+            .call()?;
+
+        let html = response.into_string()?;
+        let articles = articles(&html);
+
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse(
+                "puzzle page without description.".into(),
+            ));
+        }
+
+        let markdown: Vec<String> = articles.into_iter().map(html_to_markdown).collect();
+        Ok(markdown.join("\n\n"))
+    }
+
+    /// Submits an answer for a part and returns the verdict of the site.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Verdict, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = response.into_string()?;
+        let message = articles(&html)
+            .into_iter()
+            .map(html_to_markdown)
+            .next()
+            .ok_or_else(|| {
+                AocClientError::UnexpectedResponse("submission response without message.".into())
+            })?;
+
+        Ok(Verdict::from_response(&message))
+    }
+}
+
+/// Extracts the title from a puzzle description, e.g. `Historian Hysteria` from `## --- Day 1: Historian Hysteria ---`.
+pub fn puzzle_title(markdown: &str) -> Option<&str> {
+    let heading = markdown
+        .lines()
+        .find_map(|l| l.strip_prefix("## --- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    let title = title.trim_end().trim_end_matches("---").trim_end();
+    (!title.is_empty()).then_some(title)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The inner HTML of all `<article>` elements of a page.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(end) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + end]);
+        rest = &rest[content_start + end..];
+    }
+
+    articles
+}
+
+/// Converts the subset of HTML used by puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            out.push_str(&decode_entities(rest));
+            break;
+        };
+
+        out.push_str(&decode_entities(&rest[..tag_start]));
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let name = tag
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match name.as_str() {
+            "h2" => out.push_str("## "),
+            "/h2" => out.push_str("\n\n"),
+            "/p" => out.push_str("\n\n"),
+            // NOTE: emphasis cannot be rendered in code blocks, it would end up in the examples.
+            "em" | "/em" if !in_pre => out.push('*'),
+            "code" | "/code" if !in_pre => out.push('`'),
+            "pre" => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            "/pre" => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            "li" => out.push_str("- "),
+            "/li" => out.push('\n'),
+            "/ul" => out.push('\n'),
+            "a" => {
+                links.push(attribute(tag, "href").unwrap_or_default().to_string());
+                out.push('[');
+            }
+            "/a" => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    out.trim_end().to_string()
+}
+
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_markdown, puzzle_title, AocClientError, Client, Config};
+    use crate::{day, template::submissions::Verdict};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// A request received by the stand-in server.
+    struct Request {
+        head: String,
+        body: String,
+    }
+
+    /// Serves a single canned response on a local port, returns the base URL and the received request.
+    fn serve(status: &str, response: &'static str) -> (String, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let status = status.to_string();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+
+            let content_length = head
+                .lines()
+                .find_map(|l| {
+                    l.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|x| x.trim().to_string())
+                })
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(0);
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .unwrap();
+
+            tx.send(Request {
+                head,
+                body: String::from_utf8(body).unwrap(),
+            })
+            .unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    fn client(base_url: String) -> Client {
+        Client::new(Config {
+            base_url,
+            session: "abc".into(),
+            year: 2024,
+        })
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (base_url, requests) = serve("200 OK", "1 2\n3 4\n");
+        let input = client(base_url).input(day!(1)).unwrap();
+        assert_eq!(input, "1 2\n3 4\n");
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.head.starts_with("GET /2024/day/1/input "), true);
+        assert_eq!(request.head.contains("Cookie: session=abc"), true);
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (base_url, _requests) = serve(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>Some <em>text</em>.</p></article>\
+            <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>More.</p></article></main>",
+        );
+        let puzzle = client(base_url).puzzle(day!(1)).unwrap();
+        assert_eq!(
+            puzzle,
+            "## --- Day 1 ---\n\nSome *text*.\n\n## --- Part Two ---\n\nMore."
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, requests) = serve(
+            "200 OK",
+            "<main><article><p>That's not the right answer; your answer is too high.</p></article></main>",
+        );
+        let verdict = client(base_url).submit(day!(9), 2, "42").unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.head.starts_with("POST /2024/day/9/answer "), true);
+        assert_eq!(request.body, "level=2&answer=42");
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let (base_url, _requests) = serve("404 Not Found", "not found");
+        match client(base_url).input(day!(25)) {
+            Err(AocClientError::Http { status, body }) => {
+                assert_eq!(status, 404);
+                assert_eq!(body, "not found");
+            }
+            x => panic!("unexpected result: {x:?}"),
+        }
+    }
+
+    #[test]
+    fn extracts_puzzle_titles() {
+        assert_eq!(
+            puzzle_title("## --- Day 1: Historian Hysteria ---\n\nText."),
+            Some("Historian Hysteria")
+        );
+        assert_eq!(puzzle_title("## --- Day 1 ---\n\nText."), None);
+        assert_eq!(puzzle_title(""), None);
+    }
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html =
+            "<p>See <a href=\"/2024/day/1/input\">input</a> &amp; <code>x &lt; y</code>:</p>\
+            <pre><code>1\n2\n</code></pre><ul><li>a</li><li>b</li></ul>";
+        assert_eq!(
+            html_to_markdown(html),
+            "See [input](/2024/day/1/input) & `x < y`:\n\n```\n1\n2\n```\n\n- a\n- b"
+        );
+    }
+}
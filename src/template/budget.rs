@@ -0,0 +1,233 @@
+/// Wall-clock and memory budgets for solution stages.
+/// Budgets are passed to solution binaries as `--timeout <seconds>` and `--memory <MB>` and enforced by a
+/// watchdog thread inside the binary, which reports the offending stage and exits once a budget is exceeded.
+/// The memory budget is best-effort: the resident set size is polled every few milliseconds, so a stage that allocates
+/// quickly can exceed it before it is stopped, and it is not enforced at all outside of Linux.
+use std::{
+    env, fs, process,
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    report::{emit_report, human_print, human_println, Report, Status},
+    stats::Stats,
+    Day,
+};
+
+pub const TIMEOUT_FLAG: &str = "--timeout";
+pub const MEMORY_FLAG: &str = "--memory";
+
+/// Exit code of a solution binary that exceeded its budget.
+pub const OVER_BUDGET_EXIT_CODE: i32 = 3;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Limits that apply to each stage of a solution.
+/// The timeout covers the first run of a stage, benchmark iterations are not limited.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Budget {
+    pub timeout: Option<Duration>,
+    pub memory_mb: Option<u64>,
+}
+
+/// Parses a timeout in seconds, e.g. `1.5`. Rejects negative, infinite and NaN values instead of panicking on them.
+pub fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let seconds: f64 = value
+        .parse()
+        .map_err(|_| format!("`{value}` is not a number of seconds"))?;
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| {
+        format!("`{value}` is not a valid timeout, it must be a positive number of seconds")
+    })
+}
+
+impl Budget {
+    /// Reads the budget passed to the current solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let value_of = |flag: &str| {
+            let index = args.iter().position(|x| x == flag)?;
+            args.get(index + 1)
+        };
+
+        Budget {
+            timeout: value_of(TIMEOUT_FLAG).and_then(|x| parse_timeout(x).ok()),
+            memory_mb: value_of(MEMORY_FLAG).and_then(|x| x.parse().ok()),
+        }
+    }
+
+    /// Arguments that mirror this budget to a solution binary.
+    pub fn to_args(self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(timeout) = self.timeout {
+            args.push(TIMEOUT_FLAG.into());
+            args.push(timeout.as_secs_f64().to_string());
+        }
+
+        if let Some(memory_mb) = self.memory_mb {
+            args.push(MEMORY_FLAG.into());
+            args.push(memory_mb.to_string());
+        }
+
+        args
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.memory_mb.is_none()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The stage currently watched. `started` is unset between stages, memory is still attributed to the last stage.
+struct Watched {
+    day: Day,
+    stage: String,
+    label: String,
+    started: Option<Instant>,
+}
+
+static WATCHED: Mutex<Option<Watched>> = Mutex::new(None);
+static BUDGET: OnceLock<Budget> = OnceLock::new();
+
+/// Runs `func` as stage `stage` of `day` under the budget passed to the current binary.
+pub fn guarded<T>(day: Day, stage: &str, label: &str, func: impl FnOnce() -> T) -> T {
+    let budget = *BUDGET.get_or_init(|| {
+        let budget = Budget::from_args();
+        if !budget.is_unlimited() {
+            thread::spawn(move || watch(budget));
+        }
+        budget
+    });
+
+    if budget.is_unlimited() {
+        return func();
+    }
+
+    *WATCHED.lock().unwrap() = Some(Watched {
+        day,
+        stage: stage.into(),
+        label: label.into(),
+        started: Some(Instant::now()),
+    });
+
+    // NOTE: stop the clock even if `func` unwinds.
+    let _stop = StopClock;
+    func()
+}
+
+struct StopClock;
+
+impl Drop for StopClock {
+    fn drop(&mut self) {
+        if let Some(watched) = WATCHED.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            watched.started = None;
+        }
+    }
+}
+
+fn watch(budget: Budget) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let watched = WATCHED.lock().unwrap();
+        let Some(watched) = watched.as_ref() else {
+            continue;
+        };
+
+        let elapsed = watched.started.map(|x| x.elapsed());
+
+        if let (Some(timeout), Some(elapsed)) = (budget.timeout, elapsed) {
+            if elapsed > timeout {
+                exceeded(
+                    watched,
+                    Status::Timeout,
+                    elapsed,
+                    &format!("timed out after {timeout:.1?}"),
+                );
+            }
+        }
+
+        if let (Some(limit), Some(resident)) = (budget.memory_mb, resident_memory_mb()) {
+            if resident > limit {
+                exceeded(
+                    watched,
+                    Status::OutOfMemory,
+                    elapsed.unwrap_or_default(),
+                    &format!("exceeded memory budget ({resident}MB > {limit}MB)"),
+                );
+            }
+        }
+    }
+}
+
+fn exceeded(watched: &Watched, status: Status, elapsed: Duration, message: &str) -> ! {
+    human_print!("\r");
+    human_println!("{}: ✖ {message}", watched.label);
+
+    emit_report(&Report::failed(
+        watched.day,
+        &watched.stage,
+        status,
+        message.into(),
+        Stats::from_samples(&[elapsed]),
+    ));
+
+    process::exit(OVER_BUDGET_EXIT_CODE);
+}
+
+/// Resident set size of the current process, only available on Linux.
+fn resident_memory_mb() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes: u64 = status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kilobytes / 1024)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_timeout, resident_memory_mb, Budget};
+    use std::time::Duration;
+
+    #[test]
+    fn mirrors_budget_to_args() {
+        let budget = Budget {
+            timeout: Some(Duration::from_millis(1500)),
+            memory_mb: Some(512),
+        };
+        assert_eq!(budget.to_args(), ["--timeout", "1.5", "--memory", "512"]);
+    }
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse_timeout("1.5"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout("-1").is_err(), true);
+        assert_eq!(parse_timeout("NaN").is_err(), true);
+        assert_eq!(parse_timeout("soon").is_err(), true);
+    }
+
+    #[test]
+    fn omits_unlimited_budgets() {
+        let budget = Budget::default();
+        assert_eq!(budget.is_unlimited(), true);
+        assert_eq!(budget.to_args().is_empty(), true);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn reads_resident_memory() {
+        assert_eq!(resident_memory_mb().is_some(), true);
+    }
+}
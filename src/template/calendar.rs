@@ -0,0 +1,114 @@
+/// The puzzle calendar of an event: which days exist, when they unlock and which of them have a second part.
+/// Puzzles unlock at midnight server time (UTC-5) on consecutive days of December, starting on the 1st.
+/// The last puzzle of an event has a single part, its second star is awarded for completing all other puzzles.
+use crate::template::{year, AllDays, Day};
+
+include!("event_days.rs");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Calendar {
+    pub year: u16,
+    pub days: u8,
+}
+
+impl Calendar {
+    /// The calendar of the event of `year`.
+    pub fn of(year: u16) -> Self {
+        let days = EVENT_DAYS
+            .iter()
+            .rev()
+            .find(|(first, _)| *first <= year)
+            .map_or(EVENT_DAYS[0].1, |(_, days)| *days);
+
+        Calendar { year, days }
+    }
+
+    /// The calendar of the year a command or solution works on.
+    pub fn current() -> Self {
+        Self::of(year::current())
+    }
+
+    /// An iterator that yields every day of the event.
+    pub fn all_days(self) -> AllDays {
+        AllDays::until(self.days)
+    }
+
+    /// The day of the final puzzle.
+    pub fn last_day(self) -> Day {
+        // NOTE: every entry of `EVENT_DAYS` is a valid day number.
+        Day::__new_unchecked(self.days)
+    }
+
+    pub fn contains(self, day: Day) -> bool {
+        day <= self.days
+    }
+
+    /// Returns `true` if the puzzle of `day` has a second part.
+    pub fn has_part_two(self, day: Day) -> bool {
+        day != self.last_day()
+    }
+
+    /// Returns `day` if it is part of the event, an error for the user otherwise.
+    pub fn check(self, day: Day) -> Result<Day, String> {
+        if self.contains(day) {
+            Ok(day)
+        } else {
+            Err(format!(
+                "day {} is not part of the {} event, which has {} puzzles.",
+                day.into_inner(),
+                self.year,
+                self.days
+            ))
+        }
+    }
+
+    /// The puzzle unlocked on a date in server time, if any.
+    pub fn unlocked_on(self, year: u16, month: u8, day: u8) -> Option<Day> {
+        if year != self.year || month != 12 {
+            return None;
+        }
+
+        Day::new(day).filter(|day| self.contains(*day))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Calendar;
+    use crate::day;
+
+    #[test]
+    fn reads_days_of_events() {
+        assert_eq!(Calendar::of(2015).days, 25);
+        assert_eq!(Calendar::of(2024).days, 25);
+        assert_eq!(Calendar::of(2025).days, 12);
+        assert_eq!(Calendar::of(2030).days, 12);
+    }
+
+    #[test]
+    fn iterates_days_of_event() {
+        let days: Vec<_> = Calendar::of(2025).all_days().collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&day!(12)));
+    }
+
+    #[test]
+    fn last_day_has_one_part() {
+        assert_eq!(Calendar::of(2024).has_part_two(day!(24)), true);
+        assert_eq!(Calendar::of(2024).has_part_two(day!(25)), false);
+        assert_eq!(Calendar::of(2025).has_part_two(day!(12)), false);
+    }
+
+    #[test]
+    fn unlocks_days_of_event() {
+        let calendar = Calendar::of(2025);
+        assert_eq!(calendar.unlocked_on(2025, 12, 1), Some(day!(1)));
+        assert_eq!(calendar.unlocked_on(2025, 12, 12), Some(day!(12)));
+        assert_eq!(calendar.unlocked_on(2025, 12, 13), None);
+        assert_eq!(calendar.unlocked_on(2025, 11, 30), None);
+        assert_eq!(calendar.unlocked_on(2024, 12, 1), None);
+        assert_eq!(calendar.check(day!(13)).is_err(), true);
+    }
+}
@@ -0,0 +1,208 @@
+/// Renders timings as a self-contained SVG bar chart, one bar per solution.
+/// Bar lengths use a log scale, so that fast and slow days stay readable side by side. The stages of a solution are
+/// stacked inside its bar in proportion to their share of its total, the bar ends at the total.
+use std::{fmt::Write, time::Duration};
+
+use crate::template::timings::{Timing, Timings};
+
+const WIDTH: f64 = 800.0;
+const ROW_HEIGHT: f64 = 22.0;
+const BAR_HEIGHT: f64 = 14.0;
+const LEFT: f64 = 110.0;
+const RIGHT: f64 = 70.0;
+const TOP: f64 = 56.0;
+const BOTTOM: f64 = 36.0;
+
+/// Stages in stacking order, with their colors.
+const STAGES: [(&str, &str); 3] = [
+    ("Parse", "#8c959f"),
+    ("Part 1", "#0969da"),
+    ("Part 2", "#1a7f37"),
+];
+
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_secs_f64(nanos / 1e9))
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Durations of the stages of a timing in nanoseconds, in the order of [`STAGES`].
+fn stage_nanos(timing: &Timing) -> [Option<f64>; 3] {
+    [timing.parse_nanos, timing.part_1_nanos, timing.part_2_nanos]
+}
+
+/// Renders the chart. Solutions without recorded durations are left out.
+pub fn render(title: &str, timings: &Timings) -> String {
+    let rows: Vec<(String, [Option<f64>; 3], f64)> = timings
+        .data
+        .iter()
+        .filter_map(|timing| {
+            let stages = stage_nanos(timing);
+            let total: f64 = stages.iter().flatten().sum();
+
+            let label = match &timing.variant {
+                Some(variant) => format!("Day {} ({})", timing.day, escape_xml(variant)),
+                None => format!("Day {}", timing.day),
+            };
+
+            (total > 0.0).then_some((label, stages, total))
+        })
+        .collect();
+
+    // NOTE: the scale spans whole decades around all totals, starting at 1ns at the lowest.
+    let (min, max) = rows
+        .iter()
+        .fold((f64::MAX, 1_f64), |(min, max), (_, _, total)| {
+            (min.min(*total), max.max(*total))
+        });
+    let low = if rows.is_empty() {
+        0.0
+    } else {
+        min.max(1.0).log10().floor()
+    };
+    let high = max.log10().ceil().max(low + 1.0);
+
+    let plot_width = WIDTH - LEFT - RIGHT;
+    #[allow(clippy::cast_precision_loss)]
+    let height = TOP + BOTTOM + ROW_HEIGHT * rows.len() as f64;
+    let x = |nanos: f64| LEFT + (nanos.max(1.0).log10() - low) / (high - low) * plot_width;
+
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="system-ui, sans-serif" font-size="12">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#ffffff"/>"##
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r##"<text x="{LEFT}" y="20" font-size="15" font-weight="bold" fill="#1f2328">{}</text>"##,
+        escape_xml(title)
+    )
+    .unwrap();
+
+    let mut legend_x = LEFT;
+    for (name, color) in STAGES {
+        writeln!(
+            svg,
+            r##"<rect x="{legend_x}" y="30" width="10" height="10" fill="{color}"/><text x="{}" y="39" fill="#1f2328">{name}</text>"##,
+            legend_x + 14.0
+        )
+        .unwrap();
+        legend_x += 70.0;
+    }
+
+    // vertical grid lines and labels for every decade.
+    let axis_y = height - BOTTOM;
+    #[allow(clippy::cast_possible_truncation)]
+    for exponent in (low as i32)..=(high as i32) {
+        let position = x(10_f64.powi(exponent));
+        writeln!(
+            svg,
+            r##"<line x1="{position:.1}" y1="{TOP}" x2="{position:.1}" y2="{axis_y}" stroke="#d0d7de"/><text x="{position:.1}" y="{}" text-anchor="middle" fill="#57606a">{}</text>"##,
+            axis_y + 16.0,
+            format_nanos(10_f64.powi(exponent))
+        )
+        .unwrap();
+    }
+
+    for (i, (label, stages, total)) in rows.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = TOP + ROW_HEIGHT * i as f64 + (ROW_HEIGHT - BAR_HEIGHT) / 2.0;
+        let length = x(*total) - LEFT;
+
+        writeln!(
+            svg,
+            r##"<text x="{}" y="{}" text-anchor="end" fill="#1f2328">{label}</text>"##,
+            LEFT - 8.0,
+            y + BAR_HEIGHT - 3.0
+        )
+        .unwrap();
+
+        let mut offset = LEFT;
+        for ((name, color), nanos) in STAGES.iter().zip(stages) {
+            let Some(nanos) = nanos else {
+                continue;
+            };
+
+            let width = length * nanos / total;
+            writeln!(
+                svg,
+                r#"<rect x="{offset:.1}" y="{y:.1}" width="{width:.1}" height="{BAR_HEIGHT}" fill="{color}"><title>{label} {name}: {}</title></rect>"#,
+                format_nanos(*nanos)
+            )
+            .unwrap();
+            offset += width;
+        }
+
+        writeln!(
+            svg,
+            r##"<text x="{:.1}" y="{}" fill="#57606a">{}</text>"##,
+            offset + 6.0,
+            y + BAR_HEIGHT - 3.0,
+            format_nanos(*total)
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::{
+        day,
+        template::timings::{
+            tests::{mock_stats, mock_timing},
+            Timings,
+        },
+    };
+
+    #[test]
+    fn renders_stacked_bars_on_log_scale() {
+        let timings = Timings {
+            year: Some(2024),
+            data: vec![
+                mock_timing(
+                    day!(1),
+                    Some(mock_stats(1e3, 0.0, 10)),
+                    Some(mock_stats(1e3, 0.0, 10)),
+                ),
+                mock_timing(day!(2), Some(mock_stats(5e6, 0.0, 10)), None),
+            ],
+        };
+
+        let svg = render("2024 <Benchmarks>", &timings);
+        assert_eq!(svg.starts_with("<svg "), true);
+        assert_eq!(svg.ends_with("</svg>\n"), true);
+        assert_eq!(svg.contains("2024 &lt;Benchmarks&gt;"), true);
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert_eq!(svg.contains("<title>Day 01 Part 2: 1.0µs</title>"), true);
+        // decades from 1µs to 10ms.
+        assert_eq!(svg.contains(">1.0µs</text>"), true);
+        assert_eq!(svg.contains(">10.0ms</text>"), true);
+        assert_eq!(svg.matches("<line ").count(), 5);
+    }
+
+    #[test]
+    fn renders_empty_chart() {
+        let svg = render("empty", &Timings::default());
+        assert_eq!(svg.matches("<title>").count(), 0);
+        assert_eq!(svg.ends_with("</svg>\n"), true);
+    }
+}
@@ -1,5 +1,30 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::all_days;
+use crate::template::budget::Budget;
+use crate::template::registry::{self, Runner};
+use crate::template::run_multi::{run_multi, was_interrupted};
+
+/// Runs all days with `runner`.
+pub fn handle(is_release: bool, metrics: bool, budget: Budget, runner: Runner) {
+    if let Runner::Registry = runner {
+        registry::delegate(is_release, metrics, budget, &[]);
+    }
+
+    let run = run_multi(
+        &all_days().collect(),
+        is_release,
+        false,
+        metrics,
+        budget,
+        runner,
+    );
+
+    if was_interrupted() {
+        process::exit(130);
+    }
+
+    if run.mismatches > 0 {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,99 @@
+/// Archives the active year and starts the next one.
+/// Solutions of the active year are renamed from `src/bin/{day}.rs` to `src/bin/{year}_{day}.rs`, their data already
+/// lives in `data/{year}` and stays there. `AOC_YEAR` in `.cargo/config.toml` then points to the new year.
+use std::{fs, io, process};
+
+use crate::template::year::{self, YEAR_ENV};
+
+const CONFIG_PATH: &str = ".cargo/config.toml";
+const DATA_FOLDERS: [&str; 5] = ["inputs", "examples", "puzzles", "answers", "submissions"];
+
+/// Renames the solutions of the active year. Returns the new paths.
+fn archive_solutions(year: u16) -> io::Result<Vec<String>> {
+    let mut moves = vec![];
+
+    for entry in fs::read_dir("src/bin")? {
+        let path = entry?.path();
+        let (Some(stem), Some("rs")) = (
+            path.file_stem().and_then(|x| x.to_str()),
+            path.extension().and_then(|x| x.to_str()),
+        ) else {
+            continue;
+        };
+
+        // already archived.
+        if year::year_of_bin(stem).is_some() {
+            continue;
+        }
+
+        let target = path.with_file_name(format!("{year}_{stem}.rs"));
+        if target.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", target.display()),
+            ));
+        }
+
+        moves.push((path, target));
+    }
+
+    moves.sort();
+
+    moves
+        .into_iter()
+        .map(|(from, to)| {
+            fs::rename(&from, &to)?;
+            Ok(to.display().to_string())
+        })
+        .collect()
+}
+
+/// The cargo config with `AOC_YEAR` pointing to `next`.
+fn next_config(current: u16, next: u16) -> io::Result<String> {
+    let config = fs::read_to_string(CONFIG_PATH)?;
+    let from = format!("{YEAR_ENV} = \"{current}\"");
+
+    if !config.contains(&from) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("`{from}` not found in {CONFIG_PATH}"),
+        ));
+    }
+
+    Ok(config.replace(&from, &format!("{YEAR_ENV} = \"{next}\"")))
+}
+
+pub fn handle(next: Option<u16>) {
+    let Some(current) = year::active() else {
+        eprintln!("No active year, set {YEAR_ENV} in {CONFIG_PATH}.");
+        process::exit(1);
+    };
+
+    let next = next.unwrap_or(current + 1);
+    if next == current {
+        eprintln!("{current} is already the active year.");
+        process::exit(1);
+    }
+
+    // NOTE: check the config before anything is moved.
+    let result = next_config(current, next).and_then(|config| {
+        for path in archive_solutions(current)? {
+            println!("Archived \"{path}\"");
+        }
+
+        for folder in DATA_FOLDERS {
+            fs::create_dir_all(year::data_dir_of(next).join(folder))?;
+        }
+
+        fs::write(CONFIG_PATH, config)
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to archive {current}: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("🎄 Archived {current}, run its solutions with `cargo solve <day> --year {current}`.");
+    println!("🎄 {next} is the active year now, type `cargo scaffold <day>` to get started.");
+}
@@ -1,14 +1,39 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_client::Client, year, Day};
+use std::{fs, process};
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let client = Client::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to set up the Advent of Code client: {e}");
         process::exit(1);
-    }
+    });
+
+    let input_path = year::data_dir().join(format!("inputs/{day}.txt"));
+    let puzzle_path = year::data_dir().join(format!("puzzles/{day}.md"));
+
+    let result = client.input(day).and_then(|input| {
+        let puzzle = client.puzzle(day)?;
+        for path in [&input_path, &puzzle_path] {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        fs::write(&input_path, input)?;
+        fs::write(&puzzle_path, puzzle)?;
+        Ok(())
+    });
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = result {
+        eprintln!("Failed to download day {day}: {e}");
         process::exit(1);
-    };
+    }
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
 }
@@ -0,0 +1,159 @@
+use std::{fs, process};
+
+use crate::template::{
+    examples::{self, Example},
+    runner::confirm,
+    variants, year, Day,
+};
+
+/// Number of lines of an example shown before asking to save it.
+const PREVIEW_LINES: usize = 8;
+
+/// Asks for confirmation, unless `yes` is passed.
+fn ask(prompt: &str, yes: bool) -> bool {
+    yes || confirm(prompt)
+}
+
+/// Extracts the examples of a day from its stored puzzle description into `data/{year}/examples/{day}-{n}.txt`,
+/// and adds the confirmed answers as test cases of the solution.
+pub fn handle(day: Day, yes: bool) {
+    let data_dir = year::data_dir();
+    let puzzle_path = data_dir.join(format!("puzzles/{day}.md"));
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "No puzzle stored at \"{}\", run `cargo download {day}` or `cargo read {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let extracted = examples::extract(&puzzle);
+    if extracted.examples.is_empty() {
+        println!("The puzzle of day {day} has no examples.");
+        return;
+    }
+
+    // (part, number of the example file, answer)
+    let mut cases: Vec<(u8, usize, String)> = vec![];
+    let mut saved: Vec<(usize, &Example)> = vec![];
+
+    for example in &extracted.examples {
+        let number = saved.len() + 1;
+        let path = data_dir.join(format!("examples/{day}-{number}.txt"));
+        let lines: Vec<&str> = example.text.lines().collect();
+
+        println!(
+            "\nExample of part {} ({} lines):",
+            example.part,
+            lines.len()
+        );
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("  {line}");
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!("  …");
+        }
+
+        let unchanged = fs::read_to_string(&path).is_ok_and(|x| x == example.text);
+        let prompt = if path.exists() && !unchanged {
+            format!("Overwrite \"{}\"?", path.display())
+        } else {
+            format!("Save as \"{}\"?", path.display())
+        };
+
+        if !unchanged && !ask(&prompt, yes) {
+            continue;
+        }
+
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, &example.text));
+
+        if let Err(e) = written {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+
+        saved.push((number, example));
+
+        if let Some(answer) = &example.answer {
+            if ask(
+                &format!("Expected answer of part {}: `{answer}`?", example.part),
+                yes,
+            ) {
+                cases.push((example.part, number, answer.clone()));
+            }
+        }
+    }
+
+    // NOTE: part two without an example of its own refers to the last example of part one.
+    if let (Some(answer), Some((number, _))) = (
+        &extracted.part_two_answer,
+        saved.iter().rev().find(|(_, e)| e.part == 1),
+    ) {
+        if ask(
+            &format!("Expected answer of part 2 for example {number}: `{answer}`?"),
+            yes,
+        ) {
+            cases.push((2, *number, answer.clone()));
+        }
+    }
+
+    if cases.is_empty() {
+        return;
+    }
+
+    add_cases(day, &cases);
+}
+
+/// Adds the cases to the tests of the solution. Tests that already have cases are left alone, their cases are printed.
+fn add_cases(day: Day, cases: &[(u8, usize, String)]) {
+    let module_path = variants::find(day, None)
+        .map(|solution| format!("src/bin/{}.rs", solution.bin_name()))
+        .ok();
+    let mut source = module_path
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok());
+    let mut changed = false;
+
+    println!();
+
+    for part in [1, 2] {
+        let part_cases: Vec<String> = cases
+            .iter()
+            .filter(|(p, _, _)| *p == part)
+            .map(|(_, number, answer)| examples::format_case(*number, answer))
+            .collect();
+
+        if part_cases.is_empty() {
+            continue;
+        }
+
+        match source
+            .as_deref()
+            .and_then(|s| examples::replace_placeholder_cases(s, part, &part_cases))
+        {
+            Some(replaced) => {
+                source = Some(replaced);
+                changed = true;
+                println!(
+                    "Added {} case(s) to the tests of part {part}.",
+                    part_cases.len()
+                );
+            }
+            None => {
+                println!("Add the case(s) to the tests of part {part}:");
+                part_cases.iter().for_each(|case| println!("    {case}"));
+            }
+        }
+    }
+
+    if let (true, Some(path), Some(source)) = (changed, module_path, source) {
+        if let Err(e) = fs::write(&path, source) {
+            eprintln!("Failed to update \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
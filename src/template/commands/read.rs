@@ -1,15 +1,73 @@
-use std::process;
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    process,
+};
 
-use crate::template::{aoc_cli, Day};
+use crossterm::terminal;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::{aoc_client::Client, markdown, year, Day};
+
+/// Width that descriptions are wrapped to if the width of the terminal is unknown.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Renders the stored puzzle of a day from `data/{year}/puzzles/{day}.md` in the terminal. The puzzle is only fetched
+/// if it is not stored yet or with `refresh`, e.g. once part two is unlocked. Piped output is the plain markdown.
+pub fn handle(day: Day, part: Option<u8>, refresh: bool) {
+    if part.is_some_and(|p| !(1..=2).contains(&p)) {
+        eprintln!("`--part` must be 1 or 2.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let path = year::data_dir().join(format!("puzzles/{day}.md"));
+
+    let stored = if refresh {
+        None
+    } else {
+        fs::read_to_string(&path).ok()
     };
+    let puzzle = stored.unwrap_or_else(|| fetch(day, &path));
+
+    let shown = match part {
+        Some(part) => markdown::part(&puzzle, part).unwrap_or_else(|| {
+            eprintln!(
+                "Part two of day {day} is not stored, run `cargo read {day} --refresh` once part one is solved."
+            );
+            process::exit(1);
+        }),
+        None => &puzzle,
+    };
+
+    if !io::stdout().is_terminal() {
+        println!("{shown}");
+        return;
+    }
+
+    let width = terminal::size().map_or(DEFAULT_WIDTH, |(columns, _)| columns as usize);
+    println!("{}", markdown::render(shown, width));
+}
+
+/// Fetches the puzzle of a day and stores it at `path`.
+fn fetch(day: Day, path: &Path) -> String {
+    let client = Client::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to set up the Advent of Code client: {e}");
+        process::exit(1);
+    });
+
+    let puzzle = client.puzzle(day).unwrap_or_else(|e| {
+        eprintln!("Failed to fetch the puzzle of day {day}: {e}");
+        process::exit(1);
+    });
+
+    let stored = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, &puzzle));
+
+    if let Err(e) = stored {
+        eprintln!("Failed to store the puzzle of day {day}: {e}");
+    }
+
+    puzzle
 }
@@ -1,15 +1,23 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{aoc_client, calendar::Calendar, year, Day};
 
+/// Template of a new solution, if no named template is chosen.
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Directory of the named templates, e.g. `templates/grid.txt` for `--template grid`.
+const TEMPLATES_DIR: &str = "templates";
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,18 +27,138 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty data file. Existing files are kept, they may hold a downloaded input or a pasted example.
+fn create_data_file(path: &str, kind: &str) {
+    match safe_create_file(path, false) {
+        Ok(_) => println!("Created empty {kind} file \"{path}\""),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing {kind} file \"{path}\"");
+        }
+        Err(e) => {
+            eprintln!("Failed to create {kind} file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Reads the named template, or the built-in template without a name.
+fn read_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.to_string());
+    };
+
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+    fs::read_to_string(&path).map_err(|e| {
+        if e.kind() != ErrorKind::NotFound {
+            return format!("{}: {e}", path.display());
+        }
+
+        let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+            .into_iter()
+            .flatten()
+            .map_while(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                (path.extension()? == "txt").then_some(path.file_stem()?.to_str()?.to_string())
+            })
+            .collect();
+        names.sort();
+
+        format!(
+            "unknown template `{name}`, available: {}.",
+            if names.is_empty() {
+                "none".into()
+            } else {
+                names.join(", ")
+            }
+        )
+    })
+}
+
+/// Fills the placeholders of a template:
+///  - `%DAY_NUMBER%`: the day without padding, e.g. `5`.
+///  - `%DAY%`: the zero-padded day, e.g. `05`.
+///  - `%YEAR%`: the year of the solution.
+///  - `%PUZZLE_TITLE%`: the title of the puzzle if it was downloaded, else e.g. `Day 5`.
+fn render(template: &str, year: u16, day: Day, title: Option<&str>) -> String {
+    let day_number = day.into_inner().to_string();
+    let title = title.map_or_else(|| format!("Day {day_number}"), String::from);
+
+    template
+        .replace("%DAY_NUMBER%", &day_number)
+        .replace("%DAY%", &day.to_string())
+        .replace("%YEAR%", &year.to_string())
+        .replace("%PUZZLE_TITLE%", &title)
+}
+
+/// Turns a rendered two-part solution into one with part one only, for the last day of an event.
+/// Removes `part_two` and `test_part_two` with the attributes and comments above them, and runs part one only.
+fn drop_part_two(source: &str) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut keep = vec![true; lines.len()];
+
+    for (i, line) in lines.iter().enumerate() {
+        if !line.contains("fn part_two(") && !line.contains("fn test_part_two(") {
+            continue;
+        }
+
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        let mut start = i;
+        while start > 0 && {
+            let above = lines[start - 1].trim_start();
+            above.starts_with("#[") || above.starts_with("//")
+        } {
+            start -= 1;
+        }
+        // NOTE: also drop the blank line that separated the function from the previous item.
+        if start > 0 && lines[start - 1].trim().is_empty() {
+            start -= 1;
+        }
+
+        let end = (i..lines.len())
+            .find(|&j| lines[j] == format!("{indent}}}"))
+            .unwrap_or(lines.len() - 1);
+
+        keep[start..=end].iter_mut().for_each(|x| *x = false);
+    }
+
+    let mut rendered = lines
+        .iter()
+        .zip(keep)
+        .filter_map(|(line, keep)| keep.then_some(*line))
+        .map(|line| match line.rfind(')') {
+            Some(close) if line.contains("solution!(") => {
+                format!("{}, 1{}", &line[..close], &line[close..])
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if source.ends_with('\n') {
+        rendered.push('\n');
+    }
+    rendered
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(day: Day, overwrite: bool, template: Option<&str>) {
+    let year = year::current();
+    let data_dir = year::data_dir_of(year);
+    let input_path = data_dir.join(format!("inputs/{day}.txt"));
+    let input_path = input_path.to_string_lossy();
+    let example_path = data_dir.join(format!("examples/{day}.txt"));
+    let example_path = example_path.to_string_lossy();
+    let module_path = format!("src/bin/{}.rs", year::bin_name(year, day));
+
+    let template = read_template(template).unwrap_or_else(|e| {
+        eprintln!("Failed to read template: {e}");
+        process::exit(1);
+    });
+
+    // NOTE: the puzzle is only stored once it was downloaded or read.
+    let puzzle = fs::read_to_string(data_dir.join(format!("puzzles/{day}.md"))).ok();
+    let title = puzzle.as_deref().and_then(aoc_client::puzzle_title);
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +168,13 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let mut module = render(&template, year, day, title);
+    // NOTE: the last day of an event only has one part.
+    if !Calendar::of(year).has_part_two(day) {
+        module = drop_part_two(&module);
+    }
+
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -54,26 +184,13 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
-    }
-
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
-    }
+    create_data_file(&input_path, "input");
+    create_data_file(&example_path, "example");
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if Some(year) == year::active() {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
 }
@@ -1,9 +1,40 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{calendar::Calendar, variants, Day, INPUT_FLAG};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    variant: Option<String>,
+    release: bool,
+    dhat: bool,
+    metrics: bool,
+    input: Option<String>,
+    submit_part: Option<u8>,
+    yes: bool,
+) {
+    if input.is_some() && submit_part.is_some() {
+        eprintln!("Refusing to submit an answer computed from a custom input.");
+        process::exit(1);
+    }
+
+    let calendar = Calendar::current();
+    if submit_part == Some(2) && !calendar.has_part_two(day) {
+        eprintln!(
+            "Day {} is the last day of {}, it has no second part to submit.",
+            day.into_inner(),
+            calendar.year
+        );
+        process::exit(1);
+    }
+
+    // without `--variant`, the primary variant of a day with several solutions runs.
+    let solution = variants::find(day, variant.as_deref()).unwrap_or_else(|e| {
+        eprintln!("Failed to find the solution: {e}");
+        process::exit(1);
+    });
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), solution.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -16,13 +47,26 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if metrics {
+        cmd_args.extend(["--features".to_string(), "metrics".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
+    if let Some(input) = input {
+        cmd_args.push(INPUT_FLAG.to_string());
+        cmd_args.push(input);
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if yes {
+        cmd_args.push("--yes".to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
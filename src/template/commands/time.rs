@@ -1,16 +1,57 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fs, path::PathBuf, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::budget::Budget;
+use crate::template::compare;
+use crate::template::export::{self, Format};
+use crate::template::history::{self, Environment, Record};
+use crate::template::registry::{self, Runner};
+use crate::template::run_multi::{run_multi, was_interrupted};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Benchmarks build their solutions with the `release` profile.
+const IS_RELEASE: bool = true;
+
+/// Regression threshold of `--compare` in percent, if `--threshold` is not passed.
+pub const DEFAULT_THRESHOLD: f64 = 5.0;
+
+/// Benchmarks days. With `compare`, the results are compared against the stored timings, and the process exits
+/// with an error if a stage regressed by more than the given threshold in percent.
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: Option<f64>,
+    metrics: bool,
+    budget: Budget,
+    runner: Runner,
+) {
+    if let Runner::Registry = runner {
+        let mut args = vec!["--time".to_string()];
+        args.extend(run_all.then(|| "--all".to_string()));
+        args.extend(store.then(|| "--store".to_string()));
+        if let Some(threshold) = compare {
+            args.extend([
+                "--compare".into(),
+                "--threshold".into(),
+                threshold.to_string(),
+            ]);
+        }
+        args.extend(day.map(|day| day.to_string()));
+        registry::delegate(IS_RELEASE, metrics, budget, &args);
+    }
+
+    let stored_timings = read_timings();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare.is_some() {
+                // a comparison needs a baseline, only days that were benched before are run.
+                all_days()
+                    .filter(|day| stored_timings.data.iter().any(|t| t.day == *day))
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,12 +62,26 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: on Ctrl-C, the days that completed are still stored.
+    let run = run_multi(&days_to_run, IS_RELEASE, true, metrics, budget, runner);
+    let timings = run.timings.unwrap();
+
+    let regressions = compare.map_or(0, |threshold| {
+        print_comparison(&stored_timings, &timings, threshold)
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if !timings.data.is_empty() {
+            let environment =
+                Environment::detect(IS_RELEASE, matches!(runner, Runner::InProcess(_)));
+            if let Err(e) = history::append(&Record::new(&timings.data, environment)) {
+                eprintln!("Failed to append to benchmark history: {e}");
+            }
+        }
+
         println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
@@ -37,4 +92,90 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if was_interrupted() {
+        process::exit(130);
+    }
+
+    if run.mismatches > 0 || regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Reads the stored timings, exits if they cannot be read.
+fn read_timings() -> Timings {
+    Timings::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read stored timings: {e}");
+        process::exit(1);
+    })
+}
+
+/// Prints the changes against the baseline. Returns the number of regressions.
+fn print_comparison(baseline: &Timings, timings: &Timings, threshold: f64) -> usize {
+    let deltas = compare::compare(baseline, timings);
+
+    println!("\n{ANSI_BOLD}Compared to stored timings:{ANSI_RESET}");
+    if deltas.is_empty() {
+        println!("  No stored timings with samples to compare to.");
+        return 0;
+    }
+
+    deltas
+        .iter()
+        .for_each(|delta| println!("  {}", delta.format(threshold)));
+
+    let regressions = deltas
+        .iter()
+        .filter(|delta| delta.is_regression(threshold))
+        .count();
+
+    if regressions > 0 {
+        println!(
+            "\n{ANSI_BOLD}{regressions} stage(s) regressed by more than {threshold}%.{ANSI_RESET}"
+        );
+    }
+
+    regressions
+}
+
+/// Prints the stored benchmark history of a day, oldest first.
+pub fn show_history(day: Day) {
+    let records = history::read().unwrap_or_else(|e| {
+        eprintln!("Failed to read benchmark history: {e}");
+        process::exit(1);
+    });
+
+    let lines = history::format_day(&records, day);
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    if lines.is_empty() {
+        println!("No benchmarks stored, run `cargo time {day} --store`.");
+        return;
+    }
+
+    lines.iter().for_each(|line| println!("{line}"));
+}
+
+/// Exports the stored timings to `out`, or to stdout.
+pub fn export(format: Format, out: Option<PathBuf>) {
+    let timings = read_timings();
+    let rendered = export::render(&timings, format);
+
+    let Some(out) = out else {
+        print!("{rendered}");
+        return;
+    };
+
+    if let Err(e) = fs::write(&out, rendered) {
+        eprintln!("Failed to write {}: {e}", out.display());
+        process::exit(1);
+    }
+
+    println!(
+        "Exported {} timings to \"{}\".",
+        timings.data.len(),
+        out.display()
+    );
 }
@@ -0,0 +1,59 @@
+use std::{collections::HashSet, process};
+
+use crate::template::answers::{self, Check};
+use crate::template::budget::Budget;
+use crate::template::registry::Runner;
+use crate::template::report::Status;
+use crate::template::run_multi::{run_multi, was_interrupted};
+use crate::template::{all_days, Day};
+
+pub fn handle(day: Option<Day>, accept: bool) {
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+
+    let run = run_multi(
+        &days_to_run,
+        true,
+        false,
+        false,
+        Budget::default(),
+        Runner::processes(None),
+    );
+
+    if accept {
+        println!();
+
+        for report in &run.reports {
+            let (Some(part), Some(answer)) = (answers::part_of(report), &report.answer) else {
+                continue;
+            };
+
+            if report.status != Status::Solved {
+                continue;
+            }
+
+            let known = answers::read(report.day, part).ok().flatten();
+            if answers::check(known.as_deref(), Some(answer)) == Check::Pass {
+                continue;
+            }
+
+            match answers::store(report.day, part, answer) {
+                Ok(()) => println!("Accepted answer of day {} part {part}.", report.day),
+                Err(e) => {
+                    eprintln!(
+                        "Failed to store answer of day {} part {part}: {e}",
+                        report.day
+                    );
+                    process::exit(1);
+                }
+            }
+        }
+    }
+
+    if was_interrupted() {
+        process::exit(130);
+    }
+
+    if !accept && run.mismatches > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::{self, Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::{cursor, execute, terminal};
+
+use crate::template::{
+    report::{Report, JSON_FLAG},
+    run_multi::child_commands,
+    variants::{self, Solution},
+    watch::{self, Snapshot, Solve, Tests, FAILURE_LINES},
+    Day,
+};
+
+/// Interval in which the watched files are polled for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Interval in which a running solution is checked for having exited.
+const RUN_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Time after which a solution that has not finished is stopped, so that an endless loop does not keep a core busy.
+const SOLVE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The watched files of a day and their state at the last change.
+struct Watcher {
+    day: Day,
+    last: Snapshot,
+}
+
+impl Watcher {
+    fn new(day: Day) -> Self {
+        Watcher {
+            day,
+            last: watch::snapshot(&watch::watched_files(day)),
+        }
+    }
+
+    /// Returns the changed file, if any file changed since the last change.
+    // NOTE: new files, e.g. a second example, are picked up as changes as well.
+    fn poll(&mut self) -> Option<PathBuf> {
+        let next = watch::snapshot(&watch::watched_files(self.day));
+        let path = watch::changed(&self.last, &next)?;
+        self.last = next;
+        Some(path)
+    }
+
+    /// Blocks until a file changes and returns it.
+    fn wait(&mut self) -> PathBuf {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if let Some(path) = self.poll() {
+                return path;
+            }
+        }
+    }
+}
+
+/// Watches the files of a day. On every change, the example tests run first and, if they pass, the real input.
+pub fn handle(day: Day, variant: Option<String>, release: bool) {
+    let solution = variants::find(day, variant.as_deref()).unwrap_or_else(|e| {
+        eprintln!("Failed to find the solution: {e}");
+        process::exit(1);
+    });
+
+    let title = format!("Watching {} — press Ctrl-C to stop", solution.label());
+
+    let mut watcher = Watcher::new(day);
+    let mut trigger = None;
+
+    loop {
+        // a change while the solution runs starts over right away.
+        trigger = Some(
+            run(&solution, release, &title, trigger.as_deref(), &mut watcher)
+                .unwrap_or_else(|| watcher.wait()),
+        );
+    }
+}
+
+/// Runs the tests and the solution, redrawing the panel after every step.
+/// Returns the changed file if the solution was stopped because a file changed while it ran.
+fn run(
+    solution: &Solution,
+    release: bool,
+    title: &str,
+    trigger: Option<&Path>,
+    watcher: &mut Watcher,
+) -> Option<PathBuf> {
+    let draw = |tests: Option<&Tests>, solve: Option<&Solve>| {
+        redraw(&watch::render_panel(title, trigger, tests, solve));
+    };
+
+    draw(None, None);
+    let tests = run_tests(solution);
+    draw(Some(&tests), None);
+
+    let solve = if tests.is_success() {
+        match run_solution(solution, release, watcher) {
+            Ok(solve) => solve,
+            Err(changed) => return Some(changed),
+        }
+    } else {
+        Solve::Skipped
+    };
+    draw(Some(&tests), Some(&solve));
+    None
+}
+
+/// Clears the terminal and prints the panel from the top.
+fn redraw(lines: &[String]) {
+    let mut stdout = io::stdout();
+
+    execute!(
+        stdout,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::All)
+    )
+    .ok();
+
+    for line in lines {
+        writeln!(stdout, "{line}").ok();
+    }
+
+    stdout.flush().ok();
+}
+
+fn run_tests(solution: &Solution) -> Tests {
+    let output = Command::new("cargo")
+        .args(["test", "--color", "never", "--bin", &solution.bin_name()])
+        .output();
+
+    match output {
+        Ok(output) => watch::parse_test_output(
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        ),
+        Err(e) => Tests::BuildFailed(vec![format!("Failed to run cargo: {e}")]),
+    }
+}
+
+/// Builds the solution and runs its executable on the real input. The solution is killed once it exceeds
+/// [`SOLVE_TIMEOUT`] or a watched file changes, the changed file is returned as an error then.
+// NOTE: the executable is run directly instead of with `cargo run`, killing cargo would leave the solution running.
+fn run_solution(
+    solution: &Solution,
+    release: bool,
+    watcher: &mut Watcher,
+) -> Result<Solve, PathBuf> {
+    let executable = match child_commands::build(&[solution], release, false) {
+        Ok(mut executables) => executables.pop(),
+        Err(e) => return Ok(Solve::Failed(vec![e.to_string()])),
+    };
+
+    let Some(executable) = executable else {
+        return Ok(Solve::Failed(vec![
+            "Failed to find the built solution.".into()
+        ]));
+    };
+
+    let spawned = Command::new(executable)
+        .arg(JSON_FLAG)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            return Ok(Solve::Failed(vec![format!(
+                "Failed to run the solution: {e}"
+            )]))
+        }
+    };
+
+    // NOTE: read both pipes while the solution runs, a full pipe would block it.
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+
+    let started = Instant::now();
+    let timed_out = loop {
+        match child.try_wait() {
+            Ok(Some(_)) | Err(_) => break false,
+            Ok(None) => {}
+        }
+        if let Some(changed) = watcher.poll() {
+            stop(&mut child);
+            return Err(changed);
+        }
+        if started.elapsed() > SOLVE_TIMEOUT {
+            stop(&mut child);
+            break true;
+        }
+        thread::sleep(RUN_POLL_INTERVAL);
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    let reports: Vec<Report> = stdout
+        .lines()
+        .filter_map(|line| Report::try_from(line).ok())
+        .collect();
+
+    if timed_out {
+        return Ok(Solve::Failed(vec![format!(
+            "Stopped after {SOLVE_TIMEOUT:?}, the solution did not finish."
+        )]));
+    }
+
+    if !reports.is_empty() {
+        return Ok(Solve::Reports(reports));
+    }
+
+    // the end of the output explains why the solution did not finish, e.g. a panic or a missing input.
+    let lines: Vec<String> = stderr.lines().map(String::from).collect();
+    Ok(Solve::Failed(
+        lines[lines.len().saturating_sub(FAILURE_LINES)..].to_vec(),
+    ))
+}
+
+/// Reads a pipe of a child on a separate thread.
+fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let Some(pipe) = pipe else {
+            return String::new();
+        };
+        BufReader::new(pipe)
+            .lines()
+            .map_while(Result::ok)
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+fn stop(child: &mut Child) {
+    child.kill().ok();
+    child.wait().ok();
+}
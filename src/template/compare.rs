@@ -0,0 +1,208 @@
+/// Compares fresh benchmarks against the stored baseline, see `cargo time --compare`.
+/// Each stage with a recorded distribution on both sides reports the change of its median. Whether a change is
+/// significant is decided on the means instead: the difference of the means must exceed twice its standard error
+/// (roughly 95% confidence), computed from the samples that remain after outlier rejection, so that noise is not
+/// reported as a regression. A regression is a significant slowdown of the median by more than the configured threshold.
+use std::time::Duration;
+
+use crate::template::{
+    stats::Stats,
+    timings::{Timing, Timings},
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// The change of one stage of a solution between the baseline and the current run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub variant: Option<String>,
+    pub stage: &'static str,
+    pub before: Stats,
+    pub after: Stats,
+}
+
+impl Delta {
+    /// Change of the median in nanoseconds, positive if the stage got slower.
+    pub fn change_nanos(&self) -> f64 {
+        self.after.median_nanos - self.before.median_nanos
+    }
+
+    /// Change of the median relative to the baseline, in percent.
+    pub fn change_percent(&self) -> f64 {
+        if self.before.median_nanos == 0.0 {
+            return 0.0;
+        }
+
+        self.change_nanos() / self.before.median_nanos * 100.0
+    }
+
+    /// Returns `true` if the difference of the means is larger than the noise of both distributions.
+    /// Means and standard deviations exclude outliers, so the standard error does too.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn is_significant(&self) -> bool {
+        let variance = |s: &Stats| {
+            let kept = s.samples.saturating_sub(s.outliers).max(1);
+            s.stddev_nanos.powi(2) / kept as f64
+        };
+        let standard_error = (variance(&self.before) + variance(&self.after)).sqrt();
+
+        (self.after.mean_nanos - self.before.mean_nanos).abs() > 2.0 * standard_error
+    }
+
+    /// Returns `true` if the stage got significantly slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.is_significant() && self.change_percent() > threshold
+    }
+
+    /// Formats the delta, e.g. `Day 01 part_1  1.0ms → 1.2ms  +200.0µs (+20.0%)  ▲ regression`.
+    pub fn format(&self, threshold: f64) -> String {
+        let label = match &self.variant {
+            Some(variant) => format!("Day {} ({variant}) {}", self.day, self.stage),
+            None => format!("Day {} {}", self.day, self.stage),
+        };
+
+        let change = self.change_nanos();
+        let sign = if change < 0.0 { "-" } else { "+" };
+
+        let line = format!(
+            "{label}  {:.1?} → {:.1?}  {sign}{:.1?} ({:+.1}%)",
+            self.before.median(),
+            self.after.median(),
+            Duration::from_secs_f64(change.abs() / 1e9),
+            self.change_percent()
+        );
+
+        if self.is_regression(threshold) {
+            format!("{ANSI_BOLD}{line}  ▲ regression{ANSI_RESET}")
+        } else if self.is_significant() && change < 0.0 {
+            format!("{line}  ▼ faster")
+        } else if !self.is_significant() {
+            format!("{line}  {ANSI_ITALIC}~ noise{ANSI_RESET}")
+        } else {
+            line
+        }
+    }
+}
+
+/// The stages of a timing that have a recorded distribution.
+fn stages(timing: &Timing) -> [(&'static str, Option<Stats>); 3] {
+    [
+        ("parse", timing.parse_stats),
+        ("part_1", timing.part_1_stats),
+        ("part_2", timing.part_2_stats),
+    ]
+}
+
+/// Compares the stages of `current` against `baseline`. Stages without a distribution on both sides are skipped.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<Delta> {
+    current
+        .data
+        .iter()
+        .filter_map(|after| {
+            let before = baseline
+                .data
+                .iter()
+                .find(|t| t.day == after.day && t.variant == after.variant)?;
+            Some((before, after))
+        })
+        .flat_map(|(before, after)| {
+            stages(before).into_iter().zip(stages(after)).filter_map(
+                |((stage, before_stats), (_, after_stats))| {
+                    Some(Delta {
+                        day: after.day,
+                        variant: after.variant.clone(),
+                        stage,
+                        before: before_stats?,
+                        after: after_stats?,
+                    })
+                },
+            )
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, Delta};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{
+                tests::{mock_stats, mock_timing},
+                Timings,
+            },
+        },
+    };
+
+    fn delta(before: Stats, after: Stats) -> Delta {
+        Delta {
+            day: day!(1),
+            variant: None,
+            stage: "part_1",
+            before,
+            after,
+        }
+    }
+
+    #[test]
+    fn compares_stages_with_baseline() {
+        let baseline = Timings {
+            year: None,
+            data: vec![mock_timing(day!(1), Some(mock_stats(1e6, 1e3, 100)), None)],
+        };
+        let current = Timings {
+            year: None,
+            data: vec![mock_timing(
+                day!(1),
+                Some(mock_stats(1.5e6, 1e3, 100)),
+                Some(mock_stats(1e6, 1e3, 100)),
+            )],
+        };
+
+        let deltas = compare(&baseline, &current);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].stage, "part_1");
+        assert_eq!(deltas[0].change_nanos(), 5e5);
+        assert_eq!(deltas[0].change_percent(), 50.0);
+    }
+
+    #[test]
+    fn ignores_noise() {
+        let noisy = delta(mock_stats(1e6, 1e6, 10), mock_stats(1.2e6, 1e6, 10));
+        assert_eq!(noisy.is_significant(), false);
+        assert_eq!(noisy.is_regression(5.0), false);
+    }
+
+    #[test]
+    fn compares_means_of_kept_samples() {
+        let stats = |mean_nanos: f64, outliers: u64| Stats {
+            outliers,
+            mean_nanos,
+            ..mock_stats(1e6, 1e3, 100)
+        };
+
+        // the medians are equal, the means are not.
+        let shifted = delta(stats(1e6, 0), stats(1.002e6, 0));
+        assert_eq!(shifted.change_nanos(), 0.0);
+        assert_eq!(shifted.is_significant(), true);
+
+        // with most samples rejected as outliers, the same difference is within the noise.
+        let rejected = delta(stats(1e6, 99), stats(1.002e6, 99));
+        assert_eq!(rejected.is_significant(), false);
+    }
+
+    #[test]
+    fn detects_regressions_above_threshold() {
+        let slower = delta(mock_stats(1e6, 1e3, 100), mock_stats(1.2e6, 1e3, 100));
+        assert_eq!(slower.is_significant(), true);
+        assert_eq!(slower.is_regression(5.0), true);
+        assert_eq!(slower.is_regression(25.0), false);
+
+        let faster = delta(mock_stats(1.2e6, 1e3, 100), mock_stats(1e6, 1e3, 100));
+        assert_eq!(faster.is_regression(5.0), false);
+        assert_eq!(faster.format(5.0).ends_with("▼ faster"), true);
+    }
+}
@@ -2,13 +2,19 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::{
+    calendar::{Calendar, MAX_DAYS},
+    year,
+};
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// A valid day number of advent (i.e. an integer in range 1 to [`MAX_DAYS`]).
+/// Some events have fewer puzzles, see [`Calendar`] for the days of a given year.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +31,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAYS {
             return None;
         }
         Some(Self(day))
@@ -45,15 +51,15 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the day of the puzzle that unlocked today, if today is a day of the event of the current year.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
+        Calendar::current().unlocked_on(
+            u16::try_from(today.year()).ok()?,
+            u8::try_from(today.month()).ok()?,
+            u8::try_from(today.day()).ok()?,
+        )
     }
 }
 
@@ -94,26 +100,35 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {MAX_DAYS}")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event of the current year.
+/// Yields every day up to [`MAX_DAYS`] if no year is configured.
 pub fn all_days() -> AllDays {
-    AllDays::new()
+    year::try_current().map_or_else(AllDays::new, |year| Calendar::of(year).all_days())
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent up to [`MAX_DAYS`], or up to the last day of an event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(MAX_DAYS)
+    }
+
+    pub(crate) fn until(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(MAX_DAYS),
+        }
     }
 }
 
@@ -121,10 +136,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above `MAX_DAYS`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -139,11 +154,11 @@ impl Iterator for AllDays {
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::calendar::MAX_DAYS,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and `calendar::MAX_DAYS`"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -0,0 +1,18 @@
+// Included by `calendar.rs` and by `build.rs`, so that the registry takes the same days as `Day` and `Calendar`.
+
+/// Number of puzzles of the events, as `(first year, days)`. An event uses the last entry whose year is not after its own.
+const EVENT_DAYS: [(u16, u8); 2] = [(2015, 25), (2025, 12)];
+
+/// Number of puzzles of the longest event, the range of a [`Day`]. Whether a day is part of the event of a given year
+/// is checked by [`Calendar::check`].
+pub const MAX_DAYS: u8 = {
+    let mut max = 0;
+    let mut i = 0;
+    while i < EVENT_DAYS.len() {
+        if EVENT_DAYS[i].1 > max {
+            max = EVENT_DAYS[i].1;
+        }
+        i += 1;
+    }
+    max
+};
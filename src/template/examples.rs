@@ -0,0 +1,229 @@
+/// Extracts examples and their expected answers from a stored puzzle description, see `cargo examples`.
+/// Examples are the fenced code blocks of the description. The answer of an example is proposed from the emphasized
+/// code values (`*11*` in backticks, as converted from `<code><em>11</em></code>`) between the block and the next one,
+/// the last of them usually being the result of the example. Part two often refers back to an example of part one
+/// without a block of its own, its answer is then proposed for the last example of part one.
+use regex::Regex;
+
+/// An example of the puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// The part whose description holds the example.
+    pub part: u8,
+    pub text: String,
+    /// The proposed answer of the example.
+    pub answer: Option<String>,
+}
+
+/// Examples and answers of a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Extracted {
+    pub examples: Vec<Example>,
+    /// Proposed answer of part two for the last example of part one, if part two has no example of its own.
+    pub part_two_answer: Option<String>,
+}
+
+enum Token {
+    Block(String),
+    Value(String),
+}
+
+/// Splits a description into its code blocks and emphasized code values, each with the part it belongs to.
+fn tokenize(markdown: &str) -> Vec<(u8, Token)> {
+    // NOTE: `<code><em>` and `<em><code>` are both used for answers in descriptions.
+    let value = Regex::new(r"`\*([^*`]+)\*`|\*`([^*`]+)`\*").unwrap();
+
+    let mut tokens = vec![];
+    let mut part = 1;
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            match block.take() {
+                Some(text) => tokens.push((part, Token::Block(text))),
+                None => block = Some(String::new()),
+            }
+            continue;
+        }
+
+        if let Some(text) = block.as_mut() {
+            text.push_str(line);
+            text.push('\n');
+            continue;
+        }
+
+        if line.starts_with("## --- Part Two") {
+            part = 2;
+        }
+
+        tokens.extend(value.captures_iter(line).filter_map(|c| {
+            let value = c.get(1).or_else(|| c.get(2))?.as_str().trim();
+            Some((part, Token::Value(value.to_string())))
+        }));
+    }
+
+    tokens
+}
+
+/// Extracts the examples of a puzzle description and proposes their answers.
+pub fn extract(markdown: &str) -> Extracted {
+    let mut extracted = Extracted::default();
+    let mut part_two_value = None;
+
+    for (part, token) in tokenize(markdown) {
+        match token {
+            Token::Block(text) => extracted.examples.push(Example {
+                part,
+                text,
+                answer: None,
+            }),
+            Token::Value(value) => match extracted.examples.last_mut() {
+                Some(example) if example.part == part => example.answer = Some(value),
+                _ if part == 2 => part_two_value = Some(value),
+                _ => {}
+            },
+        }
+    }
+
+    if !extracted.examples.iter().any(|e| e.part == 2) {
+        extracted.part_two_answer = part_two_value;
+    }
+
+    extracted
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Formats a test case for an example file, e.g. `#[case(&...read_file_part("examples", DAY, 1), Some(11))]`.
+/// Numbers become literals, anything else a string.
+pub fn format_case(number: usize, answer: &str) -> String {
+    let expected = if answer.parse::<i128>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}.into()")
+    };
+
+    format!(
+        "#[case(&advent_of_code::template::read_file_part(\"examples\", DAY, {number}), Some({expected}))]"
+    )
+}
+
+/// Replaces the placeholder case of a scaffolded test of `part` with `cases`. Returns `None` if the test is not a
+/// placeholder anymore, so that hand-written cases are never touched.
+pub fn replace_placeholder_cases(source: &str, part: u8, cases: &[String]) -> Option<String> {
+    let test = match part {
+        1 => "fn test_part_one(",
+        _ => "fn test_part_two(",
+    };
+
+    let lines: Vec<&str> = source.lines().collect();
+    let test_index = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with(test))?;
+
+    let first_case = (0..test_index)
+        .rev()
+        .take_while(|i| lines[*i].trim_start().starts_with("#[case("))
+        .last()?;
+
+    let is_placeholder = lines[first_case..test_index]
+        .iter()
+        .all(|l| l.contains("read_file(\"examples\", DAY)") && l.trim_end().ends_with("None)]"));
+
+    if !is_placeholder {
+        return None;
+    }
+
+    let indent =
+        &lines[first_case][..lines[first_case].len() - lines[first_case].trim_start().len()];
+
+    let mut replaced: Vec<String> = lines[..first_case].iter().map(|l| l.to_string()).collect();
+    replaced.extend(cases.iter().map(|case| format!("{indent}{case}")));
+    replaced.extend(lines[test_index..].iter().map(|l| l.to_string()));
+
+    let mut source = replaced.join("\n");
+    source.push('\n');
+    Some(source)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, format_case, replace_placeholder_cases, Example};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Pair up the smallest number with `3`, a distance of `*2*`. In this example, the total distance is `*11*`.
+
+## --- Part Two ---
+
+So, for the example above, the similarity score is *`31`*.";
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let extracted = extract(PUZZLE);
+
+        assert_eq!(
+            extracted.examples,
+            vec![Example {
+                part: 1,
+                text: "3   4\n4   3\n".into(),
+                answer: Some("11".into()),
+            }]
+        );
+        assert_eq!(extracted.part_two_answer.as_deref(), Some("31"));
+    }
+
+    #[test]
+    fn extracts_examples_of_part_two() {
+        let puzzle = format!("{PUZZLE}\n\n```\nxmul(2,4)\n```\n\nThis adds up to `*48*`.");
+        let extracted = extract(&puzzle);
+
+        assert_eq!(extracted.examples.len(), 2);
+        assert_eq!(extracted.examples[1].part, 2);
+        assert_eq!(extracted.examples[1].answer.as_deref(), Some("48"));
+        assert_eq!(extracted.part_two_answer, None);
+    }
+
+    #[test]
+    fn formats_cases() {
+        assert_eq!(
+            format_case(1, "11"),
+            "#[case(&advent_of_code::template::read_file_part(\"examples\", DAY, 1), Some(11))]"
+        );
+        assert_eq!(
+            format_case(2, "4,6,3"),
+            "#[case(&advent_of_code::template::read_file_part(\"examples\", DAY, 2), Some(\"4,6,3\".into()))]"
+        );
+    }
+
+    #[test]
+    fn replaces_only_placeholder_cases() {
+        let source = "    #[rstest]\n    #[case(&advent_of_code::template::read_file(\"examples\", DAY), None)]\n    fn test_part_one(#[case] input: &str, #[case] expected: Option<u32>) {}\n";
+
+        let replaced =
+            replace_placeholder_cases(source, 1, &["#[case(a)]".into(), "#[case(b)]".into()])
+                .unwrap();
+        assert_eq!(
+            replaced,
+            "    #[rstest]\n    #[case(a)]\n    #[case(b)]\n    fn test_part_one(#[case] input: &str, #[case] expected: Option<u32>) {}\n"
+        );
+
+        assert_eq!(
+            replace_placeholder_cases(&replaced, 1, &["#[case(c)]".into()]),
+            None
+        );
+        assert_eq!(
+            replace_placeholder_cases(source, 2, &["#[case(c)]".into()]),
+            None
+        );
+    }
+}
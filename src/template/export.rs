@@ -0,0 +1,334 @@
+/// Exports stored timings for use outside of the README, see `cargo time --export`.
+/// CSV has one row per stage and is meant for spreadsheets, JSON-lines has one record per solution.
+/// The HTML report is a self-contained page without external resources, so it can be attached to reviews.
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    report::Status,
+    stats::Stats,
+    timings::{Timing, Timings},
+};
+
+/// Format of an export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Jsonl,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "jsonl" => Ok(Format::Jsonl),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected csv, jsonl or html."
+            )),
+        }
+    }
+}
+
+/// Renders timings in the given format.
+pub fn render(timings: &Timings, format: Format) -> String {
+    match format {
+        Format::Csv => render_csv(timings),
+        Format::Jsonl => render_jsonl(timings),
+        Format::Html => render_html(timings),
+    }
+}
+
+/// The stages of a timing: name, duration in nanoseconds, statistics and status.
+fn stages(timing: &Timing) -> [(&'static str, Option<f64>, Option<Stats>, String); 3] {
+    let status = |x: Option<Status>| x.map(|x| x.to_string()).unwrap_or_default();
+
+    [
+        (
+            "parse",
+            timing.parse_nanos,
+            timing.parse_stats,
+            status(timing.parse_status),
+        ),
+        (
+            "part_1",
+            timing.part_1_nanos,
+            timing.part_1_stats,
+            status(timing.part_1_status),
+        ),
+        (
+            "part_2",
+            timing.part_2_nanos,
+            timing.part_2_stats,
+            status(timing.part_2_status),
+        ),
+    ]
+}
+
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_secs_f64(nanos / 1e9))
+}
+
+/* -------------------------------------------------------------------------- */
+
+const CSV_HEADER: &str = "year,day,variant,stage,status,nanos,median_nanos,mean_nanos,min_nanos,p95_nanos,stddev_nanos,samples,outliers";
+
+/// Quotes a CSV field if needed.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_csv(timings: &Timings) -> String {
+    let year = timings.year.map(|x| x.to_string()).unwrap_or_default();
+    let mut csv = format!("{CSV_HEADER}\n");
+
+    for timing in &timings.data {
+        for (stage, nanos, stats, status) in stages(timing) {
+            // NOTE: stages that never ran, e.g. the parse stage of solutions without one, are left out.
+            if nanos.is_none() && stats.is_none() && status.is_empty() {
+                continue;
+            }
+
+            let mut fields = vec![
+                year.clone(),
+                timing.day.to_string(),
+                timing.variant.clone().unwrap_or_default(),
+                stage.to_string(),
+                status,
+                nanos.map(|x| x.to_string()).unwrap_or_default(),
+            ];
+
+            fields.extend(match stats {
+                Some(s) => [
+                    s.median_nanos,
+                    s.mean_nanos,
+                    s.min_nanos,
+                    s.p95_nanos,
+                    s.stddev_nanos,
+                ]
+                .iter()
+                .map(ToString::to_string)
+                .chain([s.samples.to_string(), s.outliers.to_string()])
+                .collect(),
+                None => vec![String::new(); 7],
+            });
+
+            let row: Vec<String> = fields.iter().map(|x| csv_field(x)).collect();
+            writeln!(csv, "{}", row.join(",")).unwrap();
+        }
+    }
+
+    csv
+}
+
+fn render_jsonl(timings: &Timings) -> String {
+    timings
+        .data
+        .iter()
+        .map(|timing| {
+            let mut json = JsonValue::from(timing);
+            if let (JsonValue::Object(map), Some(year)) = (&mut json, timings.year) {
+                map.insert("year".into(), JsonValue::Number(f64::from(year)));
+            }
+            // NOTE: `stringify` never fails for values built from `From<&Timing>`.
+            format!("{}\n", json.stringify().unwrap())
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+const HTML_STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #1f2328; }
+table { border-collapse: collapse; width: 100%; }
+th, td { padding: 0.3rem 0.6rem; text-align: left; white-space: nowrap; }
+tr:nth-child(even) { background: #f6f8fa; }
+td.bar { width: 50%; }
+.bar div { display: flex; height: 1rem; }
+.bar span { display: block; height: 100%; }
+.parse { background: #8c959f; } .part_1 { background: #0969da; } .part_2 { background: #1a7f37; }
+.legend span { display: inline-block; width: 0.8rem; height: 0.8rem; margin: 0 0.3rem 0 1rem; }
+tfoot { font-weight: bold; }
+";
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html(timings: &Timings) -> String {
+    let title = match timings.year {
+        Some(year) => format!("Advent of Code {year} Benchmarks"),
+        None => "Advent of Code Benchmarks".into(),
+    };
+
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(Timing::total_nanos)
+        .fold(0_f64, f64::max);
+
+    let mut rows = String::new();
+
+    for timing in &timings.data {
+        let label = match &timing.variant {
+            Some(variant) => format!("Day {} ({})", timing.day, escape_html(variant)),
+            None => format!("Day {}", timing.day),
+        };
+
+        let mut cells = String::new();
+        let mut bar = String::new();
+
+        for (stage, nanos, _, status) in stages(timing) {
+            let cell = match (nanos, status.as_str()) {
+                (Some(nanos), _) => format_nanos(nanos),
+                (None, "" | "solved") => "-".into(),
+                (None, status) => escape_html(status),
+            };
+            write!(cells, "<td>{cell}</td>").unwrap();
+
+            if let Some(nanos) = nanos.filter(|_| max_nanos > 0.0) {
+                let width = nanos / max_nanos * 100.0;
+                write!(
+                    bar,
+                    r#"<span class="{stage}" style="width: {width:.2}%" title="{stage}: {}"></span>"#,
+                    format_nanos(nanos)
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(
+            rows,
+            r#"<tr><td>{label}</td>{cells}<td>{}</td><td class="bar"><div>{bar}</div></td></tr>"#,
+            format_nanos(timing.total_nanos())
+        )
+        .unwrap();
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>{HTML_STYLE}</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p class="legend"><span class="parse"></span>Parse<span class="part_1"></span>Part 1<span class="part_2"></span>Part 2</p>
+<table>
+<thead><tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Part 2</th><th>Total</th><th></th></tr></thead>
+<tbody>
+{rows}</tbody>
+<tfoot><tr><td>Total</td><td></td><td></td><td></td><td>{}</td><td></td></tr></tfoot>
+</table>
+</body>
+</html>
+"#,
+        format_nanos(timings.total_millis() * 1e6)
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Format};
+    use crate::{
+        day,
+        template::{
+            report::Status,
+            stats::Stats,
+            timings::{tests::mock_stats, Timing, Timings},
+        },
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            year: Some(2024),
+            data: vec![Timing {
+                variant: Some("a<b,c".into()),
+                part_1_nanos: Some(1000.0),
+                part_1_stats: Some(Stats {
+                    outliers: 1,
+                    min_nanos: 900.0,
+                    mean_nanos: 1100.0,
+                    p95_nanos: 1500.0,
+                    ..mock_stats(1000.0, 50.0, 10)
+                }),
+                part_1_status: Some(Status::Solved),
+                part_2_status: Some(Status::Panicked),
+                ..Timing::new(day!(13))
+            }],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert_eq!("html".parse(), Ok(Format::Html));
+        assert_eq!("xlsx".parse::<Format>().is_err(), true);
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(&get_mock_timings(), Format::Csv);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "2024,13,\"a<b,c\",part_1,solved,1000,1000,1100,900,1500,50,10,1"
+        );
+        assert_eq!(lines[2], "2024,13,\"a<b,c\",part_2,panicked,,,,,,,,");
+    }
+
+    #[test]
+    fn renders_jsonl_and_html() {
+        let jsonl = render(&get_mock_timings(), Format::Jsonl);
+        assert_eq!(jsonl.lines().count(), 1);
+        assert_eq!(jsonl.contains(r#""year":2024"#), true);
+
+        let html = render(&get_mock_timings(), Format::Html);
+        assert_eq!(html.contains("Advent of Code 2024 Benchmarks"), true);
+        assert_eq!(html.contains("Day 13 (a&lt;b,c)"), true);
+        assert_eq!(
+            html.contains(r#"<span class="part_1" style="width: 100.00%""#),
+            true
+        );
+        assert_eq!(html.contains("<td>panicked</td>"), true);
+    }
+
+    #[test]
+    fn renders_html_bars_without_stats() {
+        let timings = Timings {
+            year: None,
+            data: vec![Timing {
+                part_1_nanos: Some(500.0),
+                part_2_nanos: Some(1500.0),
+                ..Timing::new(day!(2))
+            }],
+        };
+
+        let html = render(&timings, Format::Html);
+        assert_eq!(
+            html.contains(r#"<span class="part_1" style="width: 25.00%""#),
+            true
+        );
+        assert_eq!(
+            html.contains(r#"<span class="part_2" style="width: 75.00%""#),
+            true
+        );
+    }
+}
@@ -0,0 +1,470 @@
+/// Append-only benchmark history, kept as `data/{year}/timings_history.jsonl`.
+/// Every `cargo time --store` appends one record with the fresh timings, including their full statistics, and the
+/// environment they were taken in: git commit, rustc version, build profile and machine.
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    io::{self, ErrorKind, Write},
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    stats::Stats,
+    timings::{self, Timing},
+    year, Day,
+};
+
+/// Name of the history file in the data folder of a year.
+const HISTORY_FILE_NAME: &str = "timings_history.jsonl";
+
+/// Where and how a benchmark was taken. Values that cannot be detected are `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Environment {
+    /// Short hash of the checked out commit.
+    pub commit: Option<String>,
+    /// `true` if tracked files had uncommitted changes.
+    pub dirty: bool,
+    pub rustc: Option<String>,
+    pub profile: String,
+    /// `true` if the solutions ran in-process, see `template::registry`.
+    pub in_process: bool,
+    pub host: Option<String>,
+    pub os: String,
+    pub arch: String,
+    pub cpu: Option<String>,
+}
+
+/// Runs a command and returns its trimmed stdout, if it succeeded.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_string())
+}
+
+fn detect_host() -> Option<String> {
+    fs::read_to_string("/etc/hostname")
+        .ok()
+        .map(|x| x.trim().to_string())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| command_output("hostname", &[]))
+        .filter(|x| !x.is_empty())
+}
+
+fn detect_cpu() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok();
+    cpuinfo
+        .as_deref()
+        .and_then(|x| x.lines().find(|line| line.starts_with("model name")))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+        .or_else(|| command_output("sysctl", &["-n", "machdep.cpu.brand_string"]))
+}
+
+impl Environment {
+    /// Detects the environment of the current process. Solutions that run as their own binaries are built with the
+    /// `release` profile if `is_release` is set, in-process runs take the profile of the current binary.
+    pub fn detect(is_release: bool, in_process: bool) -> Self {
+        let is_release = if in_process {
+            !cfg!(debug_assertions)
+        } else {
+            is_release
+        };
+
+        Environment {
+            commit: command_output("git", &["rev-parse", "--short", "HEAD"]),
+            dirty: command_output("git", &["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|x| !x.is_empty()),
+            rustc: command_output("rustc", &["--version"]),
+            profile: if is_release { "release" } else { "debug" }.into(),
+            in_process,
+            host: detect_host(),
+            os: std::env::consts::OS.into(),
+            arch: std::env::consts::ARCH.into(),
+            cpu: detect_cpu(),
+        }
+    }
+}
+
+/// A stored benchmark run. `timestamp` is in seconds since the unix epoch.
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub timestamp: u64,
+    pub environment: Environment,
+    pub timings: Vec<Timing>,
+}
+
+impl Record {
+    pub fn new(timings: &[Timing], environment: Environment) -> Self {
+        Record {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |x| x.as_secs()),
+            environment,
+            timings: timings.to_vec(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn history_path() -> PathBuf {
+    year::data_dir().join(HISTORY_FILE_NAME)
+}
+
+/// Reads the history of the current year, oldest record first. A missing history is empty.
+pub fn read() -> Result<Vec<Record>, String> {
+    let path = history_path();
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, line)| {
+            Record::try_from(line).map_err(|e| format!("{}:{}: {e}", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// Appends a record to the history of the current year.
+pub fn append(record: &Record) -> io::Result<()> {
+    // NOTE: `stringify` never fails for values built from `From<&Record>`.
+    let line = JsonValue::from(record).stringify().unwrap();
+
+    let path = history_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{line}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Formats a timestamp as a UTC date and time, e.g. `2024-12-05 06:12`.
+fn format_timestamp(timestamp: u64) -> String {
+    // NOTE: converts days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let days = timestamp / 86_400;
+    let (hours, minutes) = (timestamp % 86_400 / 3600, timestamp % 3600 / 60);
+
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02} {hours:02}:{minutes:02}")
+}
+
+/// Formats a stage as its median and standard deviation, or its stored headline if no statistics were recorded.
+fn format_stage(name: &str, nanos: Option<f64>, stats: Option<&Stats>) -> Option<String> {
+    match (stats, nanos) {
+        (Some(stats), _) if stats.samples > 1 => Some(format!(
+            "{name} {:.1?} ± {:.1?}",
+            stats.median(),
+            stats.stddev()
+        )),
+        (Some(stats), _) => Some(format!("{name} {:.1?}", stats.median())),
+        (None, Some(nanos)) => Some(format!(
+            "{name} {:.1?}",
+            Duration::from_secs_f64(nanos / 1e9)
+        )),
+        (None, None) => None,
+    }
+}
+
+/// Formats the history of a day, one line per record and variant, e.g.
+/// `2024-12-05 06:12  3f2a1bc*  rustc 1.83.0  box (linux x86_64)  Part 1 1.2ms ± 20.0µs · Part 2 4.0ms ± 0.1ms`.
+/// Uncommitted changes are marked with `*`, in-process runs with `[in-process]`.
+pub fn format_day(records: &[Record], day: Day) -> Vec<String> {
+    records
+        .iter()
+        .flat_map(|record| {
+            record
+                .timings
+                .iter()
+                .filter(move |t| t.day == day)
+                .map(move |timing| (record, timing))
+        })
+        .map(|(record, timing)| {
+            let env = &record.environment;
+            let mut line = format_timestamp(record.timestamp);
+
+            let commit = env.commit.as_deref().unwrap_or("-");
+            let dirty = if env.dirty { "*" } else { "" };
+            write!(line, "  {:<8}", format!("{commit}{dirty}")).unwrap();
+            write!(line, "  {}", env.rustc.as_deref().unwrap_or("rustc ?")).unwrap();
+            write!(
+                line,
+                "  {} ({} {})",
+                env.host.as_deref().unwrap_or("?"),
+                env.os,
+                env.arch
+            )
+            .unwrap();
+
+            if env.in_process {
+                line.push_str(" [in-process]");
+            }
+
+            if let Some(variant) = &timing.variant {
+                write!(line, "  ({variant})").unwrap();
+            }
+
+            let stages: Vec<String> = [
+                ("Parse", timing.parse_nanos, timing.parse_stats.as_ref()),
+                ("Part 1", timing.part_1_nanos, timing.part_1_stats.as_ref()),
+                ("Part 2", timing.part_2_nanos, timing.part_2_stats.as_ref()),
+            ]
+            .into_iter()
+            .filter_map(|(name, nanos, stats)| format_stage(name, nanos, stats))
+            .collect();
+
+            write!(line, "  {}", stages.join(" · ")).unwrap();
+            line
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads an optional string, stored as `record.environment.<key>`.
+fn optional_string(json: &HashMap<String, JsonValue>, key: &str) -> Result<Option<String>, String> {
+    json.get(key)
+        .filter(|v| !v.is_null())
+        .map(|v| {
+            v.get::<String>().cloned().ok_or(format!(
+                "Expected record.environment.{key} to be null or string."
+            ))
+        })
+        .transpose()
+}
+
+impl From<&Environment> for JsonValue {
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let string = |x: &Option<String>| x.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("commit".into(), string(&value.commit));
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+        map.insert("rustc".into(), string(&value.rustc));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("in_process".into(), JsonValue::Boolean(value.in_process));
+        map.insert("host".into(), string(&value.host));
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("arch".into(), JsonValue::String(value.arch.clone()));
+        map.insert("cpu".into(), string(&value.cpu));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record.environment to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected record.environment.{key} to be a string."))
+        };
+
+        let boolean = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<bool>())
+                .copied()
+                .ok_or(format!(
+                    "Expected record.environment.{key} to be a boolean."
+                ))
+        };
+
+        Ok(Environment {
+            commit: optional_string(json, "commit")?,
+            dirty: boolean("dirty")?,
+            rustc: optional_string(json, "rustc")?,
+            profile: string("profile")?,
+            in_process: boolean("in_process")?,
+            host: optional_string(json, "host")?,
+            os: string("os")?,
+            arch: string("arch")?,
+            cpu: optional_string(json, "cpu")?,
+        })
+    }
+}
+
+impl From<&Record> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(timings::SCHEMA_VERSION)),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("environment".into(), JsonValue::from(&value.environment));
+        map.insert(
+            "timings".into(),
+            JsonValue::Array(value.timings.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Record {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        // NOTE: timings are stored in the schema of `timings.json`, records predating its version are version 1.
+        let version = timings::schema_version(json)?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected record.timestamp to be a number.")?;
+
+        let environment = json
+            .get("environment")
+            .ok_or("Expected record.environment to be a JSON object.")
+            .map(Environment::try_from)??;
+
+        let timings = json
+            .get("timings")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected record.timings to be an array.")?
+            .iter()
+            .map(|timing| timings::timing_from_json(timing, version))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Record {
+            timestamp: *timestamp as u64,
+            environment,
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_day, format_timestamp, Environment, Record};
+    use crate::{
+        day,
+        template::{stats::Stats, timings::Timing},
+    };
+    use tinyjson::JsonValue;
+
+    fn record() -> Record {
+        let stats = Stats {
+            samples: 100,
+            outliers: 0,
+            min_nanos: 900_000.0,
+            median_nanos: 1_000_000.0,
+            mean_nanos: 1_000_000.0,
+            p95_nanos: 1_100_000.0,
+            stddev_nanos: 20_000.0,
+        };
+
+        Record {
+            timestamp: 1_733_379_120,
+            environment: Environment {
+                commit: Some("3f2a1bc".into()),
+                dirty: true,
+                rustc: Some("rustc 1.83.0".into()),
+                profile: "release".into(),
+                in_process: false,
+                host: Some("box".into()),
+                os: "linux".into(),
+                arch: "x86_64".into(),
+                cpu: None,
+            },
+            timings: vec![Timing {
+                part_1_nanos: Some(1_000_000.0),
+                part_2_nanos: Some(2_000_000.0),
+                part_1_stats: Some(stats),
+                ..Timing::new(day!(5))
+            }],
+        }
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = record();
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let parsed = Record::try_from(line.as_str()).unwrap();
+
+        assert_eq!(parsed.timestamp, record.timestamp);
+        assert_eq!(parsed.environment, record.environment);
+        assert_eq!(parsed.timings.len(), 1);
+        assert_eq!(
+            parsed.timings[0].part_1_stats,
+            record.timings[0].part_1_stats
+        );
+        assert_eq!(Record::try_from(r#"{ "timestamp": 1 }"#).is_err(), true);
+    }
+
+    #[test]
+    fn reads_records_of_version_1() {
+        let line = r#"{ "timestamp": 1733379120, "environment": { "commit": null, "dirty": false, "rustc": null, "profile": "release", "in_process": false, "host": null, "os": "linux", "arch": "x86_64", "cpu": null }, "timings": [{ "day": "05", "part_1": "1.5ms", "part_2": null, "total_nanos": 1500000 }] }"#;
+        let parsed = Record::try_from(line).unwrap();
+
+        assert_eq!(parsed.timings[0].part_1_nanos, Some(1_500_000.0));
+        assert_eq!(parsed.timings[0].part_2_nanos, None);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_379_120), "2024-12-05 06:12");
+        assert_eq!(format_timestamp(1_709_210_096), "2024-02-29 12:34");
+    }
+
+    #[test]
+    fn formats_history_of_day() {
+        let records = [record()];
+        assert_eq!(
+            format_day(&records, day!(5)),
+            vec!["2024-12-05 06:12  3f2a1bc*  rustc 1.83.0  box (linux x86_64)  Part 1 1.0ms ± 20.0µs · Part 2 2.0ms"]
+        );
+        assert_eq!(format_day(&records, day!(6)).len(), 0);
+    }
+}
@@ -0,0 +1,269 @@
+/// Renders stored puzzle descriptions in the terminal, see `cargo read`.
+/// Only the markdown written by `aoc_client` is supported: headings, paragraphs with emphasis, inline code and links,
+/// code blocks and lists. Paragraphs and list items are wrapped to the given width, code blocks are kept as they are.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+const ANSI_CYAN: &str = "\x1b[36m";
+
+/// Heading that starts the second half of a description.
+const PART_TWO: &str = "## --- Part Two";
+
+/// Narrowest width that text is wrapped to, so that narrow terminals do not end up with a word per line.
+const MIN_WIDTH: usize = 20;
+
+/// Returns the description of a part: part one ends before the heading of part two, part two starts at it.
+/// Returns `None` for part two if it is not unlocked yet.
+pub fn part(markdown: &str, part: u8) -> Option<&str> {
+    let part_two = markdown
+        .match_indices(PART_TWO)
+        .map(|(i, _)| i)
+        .find(|i| *i == 0 || markdown[..*i].ends_with('\n'));
+
+    match (part, part_two) {
+        (2, Some(i)) => Some(&markdown[i..]),
+        (2, None) => None,
+        (_, Some(i)) => Some(markdown[..i].trim_end()),
+        (_, None) => Some(markdown),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    emphasis: bool,
+    code: bool,
+    link: bool,
+}
+
+impl Style {
+    fn paint(self, text: &str) -> String {
+        if self == Style::default() {
+            return text.to_string();
+        }
+
+        let mut painted = String::new();
+        if self.emphasis {
+            painted.push_str(ANSI_BOLD);
+        }
+        if self.code {
+            painted.push_str(ANSI_CYAN);
+        }
+        if self.link {
+            painted.push_str(ANSI_UNDERLINE);
+        }
+        painted.push_str(text);
+        painted.push_str(ANSI_RESET);
+        painted
+    }
+}
+
+/// A word of a paragraph, made of pieces that may be styled differently, e.g. `` `*11*`. ``.
+type Word = Vec<(String, Style)>;
+
+/// Splits inline markdown into styled words. Markers without a counterpart are kept as text, e.g. `a*b` in code.
+fn words(text: &str) -> Vec<Word> {
+    let chars: Vec<char> = text.chars().collect();
+    let has_after = |i: usize, c: char| chars[i + 1..].contains(&c);
+
+    let mut words: Vec<Word> = vec![];
+    let mut word: Word = vec![];
+    let mut style = Style::default();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+
+        match c {
+            '`' if style.code || has_after(i - 1, '`') => style.code = !style.code,
+            '*' if style.emphasis || has_after(i - 1, '*') => style.emphasis = !style.emphasis,
+            '[' if !style.code && has_after(i - 1, ']') => style.link = true,
+            // NOTE: the target of a link is dropped, it cannot be followed from the terminal anyway.
+            ']' if style.link => {
+                style.link = false;
+                if chars.get(i) == Some(&'(') {
+                    while i < chars.len() && chars[i] != ')' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            c if c.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => match word.last_mut() {
+                Some((piece, piece_style)) if *piece_style == style => piece.push(c),
+                _ => word.push((c.to_string(), style)),
+            },
+        }
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Wraps words to lines of at most `width` characters, unless a single word is longer.
+fn wrap(words: &[Word], width: usize, first_indent: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_indent.to_string();
+    let mut line_width = first_indent.chars().count();
+    let mut line_empty = true;
+
+    for word in words {
+        let word_width: usize = word.iter().map(|(text, _)| text.chars().count()).sum();
+
+        if !line_empty && line_width + 1 + word_width > width {
+            lines.push(std::mem::replace(&mut line, indent.to_string()));
+            line_width = indent.chars().count();
+            line_empty = true;
+        }
+
+        if !line_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        for (text, style) in word {
+            line.push_str(&style.paint(text));
+        }
+        line_width += word_width;
+        line_empty = false;
+    }
+
+    if !line_empty {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Renders a puzzle description with ANSI styling, wrapped to `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let width = width.max(MIN_WIDTH);
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut block: Option<Vec<&str>> = None;
+
+    let flush = |out: &mut Vec<String>, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            out.extend(wrap(&words(&paragraph.join(" ")), width, "", ""));
+            out.push(String::new());
+            paragraph.clear();
+        }
+    };
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            flush(&mut out, &mut paragraph);
+            match block.take() {
+                Some(lines) => {
+                    out.extend(
+                        lines
+                            .iter()
+                            .map(|l| format!("    {ANSI_CYAN}{l}{ANSI_RESET}")),
+                    );
+                    out.push(String::new());
+                }
+                None => block = Some(vec![]),
+            }
+            continue;
+        }
+
+        if let Some(lines) = block.as_mut() {
+            lines.push(line);
+            continue;
+        }
+
+        if let Some(heading) = line.strip_prefix("## ") {
+            flush(&mut out, &mut paragraph);
+            out.push(format!("{ANSI_BOLD}{}{ANSI_RESET}", heading.trim()));
+            out.push(String::new());
+        } else if let Some(item) = line.strip_prefix("- ") {
+            flush(&mut out, &mut paragraph);
+            out.extend(wrap(&words(item), width, "  • ", "    "));
+        } else if line.trim().is_empty() {
+            flush(&mut out, &mut paragraph);
+            // lists are not followed by a paragraph, they need their own separator.
+            if out.last().is_some_and(|l| !l.is_empty()) {
+                out.push(String::new());
+            }
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+
+    flush(&mut out, &mut paragraph);
+
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+
+    out.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{part, render};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is always present.
+
+```
+3   4
+4   3
+```
+
+- Pair `3` with `*4*`.
+
+## --- Part Two ---
+
+See [the list](/2024/day/1/input).";
+
+    #[test]
+    fn selects_parts() {
+        assert_eq!(part(PUZZLE, 1).unwrap().ends_with("`*4*`."), true);
+        assert_eq!(
+            part(PUZZLE, 2).unwrap().starts_with("## --- Part Two ---"),
+            true
+        );
+        assert_eq!(part(part(PUZZLE, 1).unwrap(), 2), None);
+    }
+
+    #[test]
+    fn renders_styles() {
+        assert_eq!(
+            render(PUZZLE, 80),
+            "\x1b[1m--- Day 1: Historian Hysteria ---\x1b[0m\n\
+            \n\
+            The \x1b[1mChief\x1b[0m \x1b[1mHistorian\x1b[0m is always present.\n\
+            \n    \x1b[36m3   4\x1b[0m\n    \x1b[36m4   3\x1b[0m\n\
+            \n  • Pair \x1b[36m3\x1b[0m with \x1b[1m\x1b[36m4\x1b[0m.\n\
+            \n\
+            \x1b[1m--- Part Two ---\x1b[0m\n\
+            \n\
+            See \x1b[4mthe\x1b[0m \x1b[4mlist\x1b[0m."
+        );
+    }
+
+    #[test]
+    fn wraps_paragraphs_and_lists() {
+        let rendered = render(
+            "one two three four five six seven eight nine ten eleven\n\n- one two three four five six seven",
+            24,
+        );
+        assert_eq!(
+            rendered,
+            "one two three four five\nsix seven eight nine ten\neleven\n\n  • one two three four\n    five six seven"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+/// Lightweight resource metrics of solution stages, collected when the `metrics` feature is enabled.
+/// Heap usage is counted by [`CountingAlloc`], which this crate installs as global allocator of every binary.
+/// CPU time is read from `getrusage`, the peak RSS from `/proc/self/status` (Linux only).
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Resources used by the first run of a stage.
+/// Benchmark iterations are not measured, heap counters only cover allocations made through [`CountingAlloc`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Metrics {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    pub peak_heap_bytes: u64,
+    pub peak_rss_bytes: Option<u64>,
+    pub user_cpu_nanos: u64,
+    pub sys_cpu_nanos: u64,
+}
+
+impl Metrics {
+    /// Combines the metrics of two stages. Counters are summed up, peaks are maxed.
+    pub fn combine(&self, other: &Metrics) -> Metrics {
+        Metrics {
+            allocations: self.allocations + other.allocations,
+            allocated_bytes: self.allocated_bytes + other.allocated_bytes,
+            peak_heap_bytes: self.peak_heap_bytes.max(other.peak_heap_bytes),
+            peak_rss_bytes: match (self.peak_rss_bytes, other.peak_rss_bytes) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            },
+            user_cpu_nanos: self.user_cpu_nanos + other.user_cpu_nanos,
+            sys_cpu_nanos: self.sys_cpu_nanos + other.sys_cpu_nanos,
+        }
+    }
+
+    /// Total CPU time spent in user and kernel space.
+    pub fn cpu_time(&self) -> Duration {
+        Duration::from_nanos(self.user_cpu_nanos + self.sys_cpu_nanos)
+    }
+
+    /// Formats the metrics, e.g. `[12 allocs · 1.5 KiB allocated · 1.0 KiB peak heap · 3.2 MiB peak RSS · cpu 1.0ms user / 0.0ns sys]`.
+    pub fn format(&self) -> String {
+        let mut parts = vec![
+            format!("{} allocs", self.allocations),
+            format!("{} allocated", format_bytes(self.allocated_bytes)),
+            format!("{} peak heap", format_bytes(self.peak_heap_bytes)),
+        ];
+
+        if let Some(peak_rss_bytes) = self.peak_rss_bytes {
+            parts.push(format!("{} peak RSS", format_bytes(peak_rss_bytes)));
+        }
+
+        parts.push(format!(
+            "cpu {:.1?} user / {:.1?} sys",
+            Duration::from_nanos(self.user_cpu_nanos),
+            Duration::from_nanos(self.sys_cpu_nanos)
+        ));
+
+        format!("[{}]", parts.join(" · "))
+    }
+}
+
+/// Formats a byte count with binary units, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Runs `func` and collects the resources it used. Returns no metrics if the `metrics` feature is disabled.
+#[cfg(not(feature = "metrics"))]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<Metrics>) {
+    (func(), None)
+}
+
+/// Runs `func` and collects the resources it used. Returns no metrics if the `metrics` feature is disabled.
+#[cfg(feature = "metrics")]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<Metrics>) {
+    let heap = counting::start();
+    rss::reset_peak();
+    let cpu = cpu::times();
+
+    let result = func();
+
+    let (user_cpu_nanos, sys_cpu_nanos) = cpu::elapsed_since(cpu);
+    let (allocations, allocated_bytes, peak_heap_bytes) = counting::since(heap);
+
+    let metrics = Metrics {
+        allocations,
+        allocated_bytes,
+        peak_heap_bytes,
+        peak_rss_bytes: rss::peak_bytes(),
+        user_cpu_nanos,
+        sys_cpu_nanos,
+    };
+
+    (result, Some(metrics))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "metrics")]
+pub use counting::CountingAlloc;
+
+// NOTE: installed by the library rather than the `solution!` macro, so that a binary can hold several solutions.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+// NOTE: dhat takes precedence, heap metrics are reported as zero then.
+#[cfg(all(feature = "metrics", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[cfg(feature = "metrics")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+    static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+    static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+    /// A global allocator that wraps [`System`] and counts allocations.
+    pub struct CountingAlloc;
+
+    fn record(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                // NOTE: a reallocation counts as freeing the old block and allocating the new one.
+                CURRENT_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+                record(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    /// Counter values at the start of a measurement.
+    pub struct Snapshot {
+        allocations: u64,
+        allocated_bytes: u64,
+        current_bytes: u64,
+    }
+
+    /// Starts a measurement, the heap peak is reset to the current heap size.
+    pub fn start() -> Snapshot {
+        let current_bytes = CURRENT_BYTES.load(Ordering::Relaxed);
+        PEAK_BYTES.store(current_bytes, Ordering::Relaxed);
+
+        Snapshot {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+            current_bytes,
+        }
+    }
+
+    /// Returns allocations, allocated bytes and peak heap growth since `snapshot`.
+    pub fn since(snapshot: Snapshot) -> (u64, u64, u64) {
+        (
+            ALLOCATIONS.load(Ordering::Relaxed) - snapshot.allocations,
+            ALLOCATED_BYTES.load(Ordering::Relaxed) - snapshot.allocated_bytes,
+            PEAK_BYTES
+                .load(Ordering::Relaxed)
+                .saturating_sub(snapshot.current_bytes),
+        )
+    }
+}
+
+#[cfg(feature = "metrics")]
+mod cpu {
+    /// User and system CPU time of the calling thread in nanoseconds.
+    pub fn times() -> (u64, u64) {
+        // NOTE: only count the thread that runs the solution, not the budget watchdog.
+        #[cfg(target_os = "linux")]
+        let who = libc::RUSAGE_THREAD;
+        #[cfg(not(target_os = "linux"))]
+        let who = libc::RUSAGE_SELF;
+
+        // SAFETY: `getrusage` only writes to the zero-initialized struct passed to it.
+        let usage = unsafe {
+            let mut usage: libc::rusage = std::mem::zeroed();
+            if libc::getrusage(who, &mut usage) != 0 {
+                return (0, 0);
+            }
+            usage
+        };
+
+        let nanos = |t: libc::timeval| t.tv_sec as u64 * 1_000_000_000 + t.tv_usec as u64 * 1_000;
+        (nanos(usage.ru_utime), nanos(usage.ru_stime))
+    }
+
+    pub fn elapsed_since(start: (u64, u64)) -> (u64, u64) {
+        let end = times();
+        (end.0.saturating_sub(start.0), end.1.saturating_sub(start.1))
+    }
+}
+
+#[cfg(feature = "metrics")]
+mod rss {
+    use std::fs;
+
+    /// Resets the peak RSS of the process, so that it covers the following stage only. Requires Linux 4.0+.
+    pub fn reset_peak() {
+        let _ = fs::write("/proc/self/clear_refs", "5");
+    }
+
+    /// Peak resident set size of the process.
+    pub fn peak_bytes() -> Option<u64> {
+        let status = fs::read_to_string("/proc/self/status").ok()?;
+        let kilobytes: u64 = status
+            .lines()
+            .find_map(|l| l.strip_prefix("VmHWM:"))?
+            .trim()
+            .strip_suffix("kB")?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(kilobytes * 1024)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Metrics> for JsonValue {
+    fn from(value: &Metrics) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert(
+            "allocated_bytes".into(),
+            JsonValue::Number(value.allocated_bytes as f64),
+        );
+        map.insert(
+            "peak_heap_bytes".into(),
+            JsonValue::Number(value.peak_heap_bytes as f64),
+        );
+        map.insert(
+            "peak_rss_bytes".into(),
+            value
+                .peak_rss_bytes
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert(
+            "user_cpu_nanos".into(),
+            JsonValue::Number(value.user_cpu_nanos as f64),
+        );
+        map.insert(
+            "sys_cpu_nanos".into(),
+            JsonValue::Number(value.sys_cpu_nanos as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Metrics {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected metrics to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected metrics.{key} to be a number."))
+        };
+
+        let peak_rss_bytes = json
+            .get("peak_rss_bytes")
+            .filter(|v| !v.is_null())
+            .map(|_| number("peak_rss_bytes"))
+            .transpose()?;
+
+        Ok(Metrics {
+            allocations: number("allocations")?,
+            allocated_bytes: number("allocated_bytes")?,
+            peak_heap_bytes: number("peak_heap_bytes")?,
+            peak_rss_bytes,
+            user_cpu_nanos: number("user_cpu_nanos")?,
+            sys_cpu_nanos: number("sys_cpu_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, Metrics};
+    use tinyjson::JsonValue;
+
+    fn mock_metrics() -> Metrics {
+        Metrics {
+            allocations: 12,
+            allocated_bytes: 1536,
+            peak_heap_bytes: 1024,
+            peak_rss_bytes: Some(3 * 1024 * 1024),
+            user_cpu_nanos: 1_000_000,
+            sys_cpu_nanos: 0,
+        }
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+
+    #[test]
+    fn formats_metrics() {
+        assert_eq!(
+            mock_metrics().format(),
+            "[12 allocs · 1.5 KiB allocated · 1.0 KiB peak heap · 3.0 MiB peak RSS · cpu 1.0ms user / 0.0ns sys]"
+        );
+    }
+
+    #[test]
+    fn combines_stages() {
+        let other = Metrics {
+            peak_rss_bytes: None,
+            peak_heap_bytes: 4096,
+            ..mock_metrics()
+        };
+        let combined = mock_metrics().combine(&other);
+        assert_eq!(combined.allocations, 24);
+        assert_eq!(combined.peak_heap_bytes, 4096);
+        assert_eq!(combined.peak_rss_bytes, Some(3 * 1024 * 1024));
+        assert_eq!(combined.user_cpu_nanos, 2_000_000);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let metrics = mock_metrics();
+        let json = JsonValue::from(&metrics);
+        assert_eq!(Metrics::try_from(&json).unwrap(), metrics);
+    }
+}
@@ -1,35 +1,92 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    io::{self, Read},
+    process,
+};
 
-pub mod aoc_cli;
+pub mod aoc_client;
+pub mod budget;
+pub mod calendar;
 pub mod commands;
+pub mod export;
+pub mod metrics;
+pub mod registry;
 pub mod runner;
+pub mod year;
 
 pub use day::*;
 
+mod answers;
+mod chart;
+mod compare;
 mod day;
+mod examples;
+mod history;
+mod markdown;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod variants;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the current year to a string, e.g. `data/2024/examples/01.txt`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(year::data_dir())
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Passes an input file to a solution binary, `-` reads the input from stdin.
+pub const INPUT_FLAG: &str = "--input";
+
+/// Reads the puzzle input of a solution binary.
+/// Defaults to `data/{year}/inputs/{day}.txt`, `--input <path>` reads any file instead and `--input -` reads stdin.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == INPUT_FLAG) else {
+        return read_file("inputs", day);
+    };
+
+    let Some(path) = args.get(index + 1) else {
+        eprintln!(
+            "Missing value for {INPUT_FLAG}. Format: cargo solve 1 {INPUT_FLAG} path/to/input.txt"
+        );
+        process::exit(1);
+    };
+
+    let input = if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map(|_| input)
+    } else {
+        fs::read_to_string(path)
+    };
+
+    input.unwrap_or_else(|e| {
+        eprintln!("Failed to read input `{path}`: {e}");
+        process::exit(1);
+    })
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(year::data_dir())
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
@@ -39,6 +96,10 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A parse stage can be added with `parse = <function>`, e.g. `solution!(16, parse = parse_input)`.
+/// Its output is timed separately and both parts then take a reference to it instead of the raw input. Benchmarks parse
+/// the input again for every sample, so a part should not rely on results cached in it by the other part.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +111,64 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@header $day:expr, $( $part:expr )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The parts this solution runs.
+        pub const PARTS: &[u8] = &[$( $part ),*];
 
         fn main() {
+            // NOTE: one profiler for the whole process, it writes `dhat-heap.json` when it is dropped.
+            #[cfg(feature = "dhat-heap")]
+            let profiler = dhat::Profiler::new_heap();
+
+            let input = $crate::template::read_input(DAY);
+            let ok = run_parts(&input, PARTS);
+
+            // `process::exit` does not run destructors.
+            #[cfg(feature = "dhat-heap")]
+            drop(profiler);
+
+            if !ok {
+                std::process::exit($crate::template::runner::PANIC_EXIT_CODE);
+            }
+        }
+    };
+
+    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day, $( $part )*);
+
+        /// Runs the given parts of the solution, used by `main` and the solution registry.
+        pub fn run_parts(input: &str, parts: &[u8]) -> bool {
+            use $crate::template::runner::*;
+            $( if parts.contains(&$part) { run_part($func, input, DAY, $part); } )*
+            true
+        }
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day, $( $part )*);
+
+        /// Runs the given parts of the solution, used by `main` and the solution registry.
+        /// Returns `false` if the parse stage panicked.
+        pub fn run_parts(input: &str, parts: &[u8]) -> bool {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let Some(parsed) = run_parse($parse, input, DAY) else {
+                return false;
+            };
+            $( if parts.contains(&$part) { run_parsed_part($func, &parsed, $parse, input, DAY, $part); } )*
+            true
         }
     };
 }
@@ -1,11 +1,24 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+/// Each table is delimited by a pair of markers, e.g. `<!--- benchmarking table 2024 --->`. A single marker is a
+/// placeholder for a new table. A README can hold any number of tables, e.g. one per year or several of one year.
+/// Markers can configure their table with options after the year:
+///  - `columns=total,share,samples,allocs,variant` adds the per-day total, its share of the time of all rows, the
+///    number of samples of each part, the allocations, and the variant. With a variant column, every variant of a day
+///    is listed, otherwise only its primary variant.
+///  - `sort=day` (default) or `sort=time`, which lists the slowest day first.
+///  - `chart=path/to/chart.svg` renders the table as an SVG chart to the path, relative to the README, and links it
+///    below the table.
+use std::{collections::HashMap, fs, io, path::Path, time::Duration};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::chart;
+use crate::template::metrics::{format_bytes, Metrics};
+use crate::template::stats::Stats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{variants, year, Day};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+const MARKER_PREFIX: &str = "<!--- benchmarking table";
+const MARKER_SUFFIX: &str = "--->";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -20,108 +33,500 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// An optional column of a table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    Total,
+    Share,
+    Samples,
+    Allocs,
+    Variant,
+}
+
+impl Column {
+    const ALL: [(&'static str, Column); 5] = [
+        ("total", Column::Total),
+        ("share", Column::Share),
+        ("samples", Column::Samples),
+        ("allocs", Column::Allocs),
+        ("variant", Column::Variant),
+    ];
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Sort {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Time,
+}
+
+/// Options of a table, configured in its marker.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct TableOptions {
+    columns: Vec<Column>,
+    sort: Sort,
+    chart: Option<String>,
+}
+
+impl TableOptions {
+    fn has(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+fn marker(year: Option<u16>, options: &TableOptions) -> String {
+    let mut marker = MARKER_PREFIX.to_string();
+
+    if let Some(year) = year {
+        marker.push_str(&format!(" {year}"));
+    }
+
+    if !options.columns.is_empty() {
+        let names: Vec<&str> = options
+            .columns
+            .iter()
+            .filter_map(|c| {
+                Column::ALL
+                    .iter()
+                    .find(|(_, x)| x == c)
+                    .map(|(name, _)| *name)
+            })
+            .collect();
+        marker.push_str(&format!(" columns={}", names.join(",")));
+    }
+
+    if options.sort == Sort::Time {
+        marker.push_str(" sort=time");
+    }
+
+    if let Some(chart) = &options.chart {
+        marker.push_str(&format!(" chart={chart}"));
+    }
+
+    format!("{marker} {MARKER_SUFFIX}")
+}
+
+/// Parses the year and options of a marker, e.g. `<!--- benchmarking table 2024 sort=time --->`.
+fn parse_marker(marker: &str) -> Result<(Option<u16>, TableOptions), Error> {
+    let inner = marker
+        .strip_prefix(MARKER_PREFIX)
+        .and_then(|x| x.strip_suffix(MARKER_SUFFIX))
+        .ok_or_else(|| Error::Parser(format!("{marker}: not a benchmarking table marker.")))?;
+
+    let mut year = None;
+    let mut options = TableOptions::default();
+
+    for (i, token) in inner.split_whitespace().enumerate() {
+        let unknown = || Error::Parser(format!("{marker}: unknown option `{token}`."));
+
+        match token.split_once('=') {
+            None if i == 0 => year = Some(token.parse().map_err(|_| unknown())?),
+            Some(("columns", columns)) => {
+                options.columns = columns
+                    .split(',')
+                    .map(|name| {
+                        Column::ALL
+                            .iter()
+                            .find(|(x, _)| *x == name)
+                            .map(|(_, column)| *column)
+                            .ok_or_else(unknown)
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+            Some(("sort", "day")) => options.sort = Sort::Day,
+            Some(("sort", "time")) => options.sort = Sort::Time,
+            Some(("chart", path)) if !path.is_empty() => options.chart = Some(path.into()),
+            _ => return Err(unknown()),
+        }
+    }
+
+    Ok((year, options))
+}
+
+/// A table of the README, between `pos_start` and `pos_end`. A placeholder table is empty.
 pub struct TablePosition {
+    year: Option<u16>,
+    options: TableOptions,
     pos_start: usize,
     pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Option<u16>, day: Day, variant: Option<&str>) -> String {
+    let base = match year {
+        Some(year) => year::bin_name(year, day),
+        None => day.to_string(),
+    };
+
+    match variant {
+        Some(variant) => format!("./src/bin/{base}_{variant}.rs"),
+        None => format!("./src/bin/{base}.rs"),
+    }
+}
+
+/// Keeps one timing per day: the configured primary variant, else the plain solution, else the first variant.
+fn primary_timings(timings: Timings, configured: &HashMap<Day, String>) -> Timings {
+    let mut by_day: Vec<(Day, Vec<Timing>)> = vec![];
+    for timing in timings.data {
+        match by_day.iter_mut().find(|(day, _)| *day == timing.day) {
+            Some((_, group)) => group.push(timing),
+            None => by_day.push((timing.day, vec![timing])),
+        }
+    }
+
+    let data = by_day
+        .into_iter()
+        .filter_map(|(day, mut group)| {
+            let configured = configured.get(&day).map(String::as_str);
+            let index = group
+                .iter()
+                .position(|t| configured.is_some() && t.variant.as_deref() == configured)
+                .or_else(|| group.iter().position(|t| t.variant.is_none()))
+                .unwrap_or(0);
+            (!group.is_empty()).then(|| group.swap_remove(index))
+        })
+        .collect();
+
+    Timings {
+        year: timings.year,
+        data,
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds all tables of the README, in order. A marker must occur once (a placeholder) or twice (a table).
+fn locate_tables(readme: &str) -> Result<Vec<TablePosition>, Error> {
+    let mut markers: Vec<(&str, Vec<usize>)> = vec![];
+    let mut offset = 0;
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+    while let Some(pos) = readme[offset..].find(MARKER_PREFIX).map(|x| x + offset) {
+        let end = readme[pos..]
+            .find(MARKER_SUFFIX)
+            .map(|x| pos + x + MARKER_SUFFIX.len())
+            .ok_or_else(|| Error::Parser("Could not find end of table marker.".into()))?;
+
+        let text = &readme[pos..end];
+        match markers.iter_mut().find(|(x, _)| *x == text) {
+            Some((_, positions)) => positions.push(pos),
+            None => markers.push((text, vec![pos])),
+        }
+
+        offset = end;
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    markers
+        .into_iter()
+        .map(|(text, positions)| {
+            if positions.len() > 2 {
+                return Err(Error::Parser(format!(
+                    "{text}: too many occurences of marker in README."
+                )));
+            }
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+            let (year, options) = parse_marker(text)?;
+            Ok(TablePosition {
+                year,
+                options,
+                pos_start: positions[0],
+                pos_end: positions[positions.len() - 1] + text.len(),
+            })
+        })
+        .collect()
+}
+
+/// Formats a part's timing as the headline duration followed by its standard deviation, if known.
+fn format_cell(nanos: Option<f64>, stats: Option<&Stats>) -> String {
+    let duration = match nanos {
+        Some(nanos) => format!("`{:.1?}`", Duration::from_secs_f64(nanos / 1e9)),
+        None => "`-`".into(),
+    };
 
-    Ok(TablePosition { pos_start, pos_end })
+    match stats {
+        Some(stats) if stats.samples > 1 => format!("{duration} ±{:.1?}", stats.stddev()),
+        _ => duration,
+    }
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Formats the combined resource metrics of a day as the cells of the metric columns.
+/// The allocations can be shown without the other metric columns.
+fn format_metric_cells(metrics: Option<Metrics>, with_allocs: bool, with_others: bool) -> String {
+    let mut cells = String::new();
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    if with_allocs {
+        match metrics {
+            Some(metrics) => cells.push_str(&format!(" `{}` |", metrics.allocations)),
+            None => cells.push_str(" `-` |"),
+        }
+    }
+
+    if !with_others {
+        return cells;
+    }
+
+    let Some(metrics) = metrics else {
+        return cells + " `-` | `-` | `-` |";
+    };
+
+    let peak_rss = metrics
+        .peak_rss_bytes
+        .map_or_else(|| "-".into(), format_bytes);
+
+    format!(
+        "{cells} `{}` | `{}` | `{:.1?}` |",
+        format_bytes(metrics.peak_heap_bytes),
+        peak_rss,
+        metrics.cpu_time()
+    )
+}
+
+/// Formats the number of samples of the parts of a day, e.g. `100 / 20`.
+fn format_samples(timing: &Timing) -> String {
+    let samples =
+        |stats: Option<Stats>| stats.map_or_else(|| "-".into(), |s| s.samples.to_string());
+    format!(
+        "`{} / {}`",
+        samples(timing.part_1_stats),
+        samples(timing.part_2_stats)
+    )
+}
+
+fn title(year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("{year} Benchmarks"),
+        None => "Benchmarks".into(),
+    }
+}
+
+/// Sorts the timings of a table.
+fn sort_timings(mut timings: Timings, sort: Sort) -> Timings {
+    if sort == Sort::Time {
+        timings
+            .data
+            .sort_by(|a, b| b.total_nanos().total_cmp(&a.total_nanos()));
+    }
+    timings
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    options: &TableOptions,
+    total_millis: f64,
+) -> String {
+    let marker = marker(timings.year, options);
+    let title = title(timings.year);
+    let header = format!("{prefix} {title}");
+
+    // only show the parse column if at least one solution has a parse stage.
+    let has_parse = timings.data.iter().any(|t| t.parse_nanos.is_some());
+    // only show the metric columns if at least one solution was timed with `--metrics`.
+    let has_metrics = timings.data.iter().any(|t| t.total_metrics().is_some());
+    let has_allocs = has_metrics || options.has(Column::Allocs);
+
+    // NOTE: tables with a variant column list every variant, shares are relative to what the table lists.
+    let table_millis = timings.total_millis();
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+
+    let mut columns = vec!["Day"];
+    if options.has(Column::Variant) {
+        columns.push("Variant");
+    }
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    for (column, name) in [
+        (Column::Total, "Total"),
+        (Column::Share, "Share"),
+        (Column::Samples, "Samples"),
+    ] {
+        if options.has(column) {
+            columns.push(name);
+        }
+    }
+    if has_allocs {
+        columns.push("Allocs");
+    }
+    if has_metrics {
+        columns.extend(["Peak heap", "Peak RSS", "CPU"]);
+    }
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!(
+        "|{} :---:  |",
+        " :---: |".repeat(columns.len() - 1)
+    ));
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(timings.year, timing.day, timing.variant.as_deref());
+        let metrics = format_metric_cells(timing.total_metrics(), has_allocs, has_metrics);
+
+        let mut cells = String::new();
+        if options.has(Column::Variant) {
+            cells.push_str(&format!(
+                " `{}` |",
+                timing.variant.as_deref().unwrap_or("-")
+            ));
+        }
+        if has_parse {
+            cells.push_str(&format!(
+                " {} |",
+                format_cell(timing.parse_nanos, timing.parse_stats.as_ref())
+            ));
+        }
+        cells.push_str(&format!(
+            " {} | {} |",
+            format_cell(timing.part_1_nanos, timing.part_1_stats.as_ref()),
+            format_cell(timing.part_2_nanos, timing.part_2_stats.as_ref()),
+        ));
+        if options.has(Column::Total) {
+            cells.push_str(&format!(
+                " `{:.1?}` |",
+                Duration::from_secs_f64(timing.total_nanos() / 1e9)
+            ));
+        }
+        if options.has(Column::Share) {
+            let share = if table_millis > 0.0 {
+                timing.total_nanos() / (table_millis * 1e4)
+            } else {
+                0.0
+            };
+            cells.push_str(&format!(" `{share:.1}%` |"));
+        }
+        if options.has(Column::Samples) {
+            cells.push_str(&format!(" {} |", format_samples(&timing)));
+        }
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{cells}{metrics}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
         ));
     }
 
+    // NOTE: `total_millis` only counts the primary variants, tables with a variant column total what they list.
+    let shown_millis = if options.has(Column::Variant) {
+        table_millis
+    } else {
+        total_millis
+    };
+
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(format!("**Total: {shown_millis:.2}ms**"));
+    if let Some(chart) = &options.chart {
+        lines.push(String::new());
+        lines.push(format!("![{title}]({chart})"));
+    }
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+/// Replaces the tables of the year of `timings`, each with its own options.
+/// If the README has no table for the year yet, the table of a single-year README (without a year in its marker)
+/// is taken over. Failing that,
+/// a table is appended if the README already has tables of other years.
+/// Tables list the `primary` variant of each day, unless they have a variant column.
+/// Returns the charts of the tables to write, as their path and content.
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    primary: &HashMap<Day, String>,
+    total_millis: f64,
+) -> Result<Vec<(String, String)>, Error> {
+    let (mut own, others): (Vec<_>, Vec<_>) = locate_tables(s)?
+        .into_iter()
+        .partition(|t| t.year == timings.year);
+
+    if own.is_empty() {
+        if let Some(legacy) = others.into_iter().find(|t| t.year.is_none()) {
+            own.push(legacy);
+        } else if timings.year.is_some() && s.contains(MARKER_PREFIX) {
+            let pos_start = s.trim_end().len();
+            s.truncate(pos_start);
+            s.push_str("\n\n\n");
+            own.push(TablePosition {
+                year: timings.year,
+                options: TableOptions::default(),
+                pos_start: s.len() - 1,
+                pos_end: s.len() - 1,
+            });
+        } else {
+            return Err(Error::Parser("Could not find table start position.".into()));
+        }
+    }
+
+    let mut charts = vec![];
+
+    // NOTE: replace from the back, so that the positions of the tables before stay valid.
+    for table in own.iter().rev() {
+        let timings = if table.options.has(Column::Variant) {
+            timings.clone()
+        } else {
+            primary_timings(timings.clone(), primary)
+        };
+        let timings = sort_timings(timings, table.options.sort);
+
+        if let Some(path) = &table.options.chart {
+            charts.push((path.clone(), chart::render(&title(timings.year), &timings)));
+        }
+
+        let content = construct_table("##", timings, &table.options, total_millis);
+        s.replace_range(table.pos_start..table.pos_end, &content);
+    }
+
+    Ok(charts)
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let primary = variants::read_primary();
+    let total_millis = primary_timings(timings.clone(), &primary).total_millis();
+    let charts = update_content(&mut readme, timings, &primary, total_millis)?;
+
+    for (chart_path, svg) in charts {
+        if let Some(parent) = Path::new(&chart_path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(chart_path, svg)?;
+    }
+
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{parse_marker, primary_timings, update_content, Column, Sort, TableOptions};
+    use crate::{
+        day, template::metrics::Metrics, template::stats::Stats, template::timings::Timing,
+        template::timings::Timings,
+    };
+    use std::collections::HashMap;
+
+    /// Marker of the table of a single-year README, taken over by the table of a year on update.
+    const MARKER: &str = "<!--- benchmarking table --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
+            year: None,
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1_nanos: Some(1e7),
+                    part_2_nanos: Some(2e7),
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1_nanos: Some(3e7),
+                    part_2_nanos: Some(4e7),
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1_nanos: Some(4e7),
+                    part_2_nanos: Some(5e7),
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -131,36 +536,253 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &HashMap::new(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &HashMap::new(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &HashMap::new(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &HashMap::new(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &HashMap::new(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn formats_spread_if_present() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(Stats {
+            samples: 100,
+            outliers: 0,
+            min_nanos: 9e6,
+            median_nanos: 1e7,
+            mean_nanos: 1e7,
+            p95_nanos: 1.1e7,
+            stddev_nanos: 5e5,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, &HashMap::new(), 190.0).unwrap();
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` ±500.0µs | `20.0ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn adds_parse_column_if_present() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse_nanos = Some(5e6);
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, &HashMap::new(), 190.0).unwrap();
+        assert_eq!(s.contains("| Day | Parse | Part 1 | Part 2 |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn adds_metric_columns_if_present() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_metrics = Some(Metrics {
+            allocations: 10,
+            allocated_bytes: 4096,
+            peak_heap_bytes: 2048,
+            peak_rss_bytes: Some(4 * 1024 * 1024),
+            user_cpu_nanos: 9_000_000,
+            sys_cpu_nanos: 1_000_000,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, &HashMap::new(), 190.0).unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Allocs | Peak heap | Peak RSS | CPU |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `10` | `2.0 KiB` | `4.0 MiB` | `10.0ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | `-` | `-` | `-` | `-` |"
+            ),
+            true
+        );
+    }
+
+    #[test]
+    fn replaces_single_year_table_with_year_table() {
+        let mut timings = get_mock_timings();
+        timings.year = Some(2015);
+
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, &HashMap::new(), 190.0).unwrap();
+        assert_eq!(s.contains(MARKER), false);
+        assert_eq!(s.matches("<!--- benchmarking table 2015 --->").count(), 2);
+        assert_eq!(s.contains("## 2015 Benchmarks"), true);
+        assert_eq!(s.contains("| [Day 1](./src/bin/2015_01.rs) |"), true);
+    }
+
+    #[test]
+    fn appends_table_of_new_year() {
+        let mut timings = get_mock_timings();
+        timings.year = Some(2015);
+        let mut s = "foo\n".to_string();
+        let mut other = get_mock_timings();
+        other.year = Some(2016);
+        s.push_str(&format!(
+            "{}{}\n",
+            super::marker(other.year, &TableOptions::default()),
+            super::marker(other.year, &TableOptions::default())
+        ));
+
+        update_content(&mut s, other, &HashMap::new(), 190.0).unwrap();
+        update_content(&mut s, timings, &HashMap::new(), 190.0).unwrap();
+        assert_eq!(s.contains("## 2015 Benchmarks"), true);
+        assert_eq!(s.contains("## 2016 Benchmarks"), true);
+        assert_eq!(s.ends_with("<!--- benchmarking table 2015 --->\n"), true);
+    }
+
+    #[test]
+    fn lists_primary_variants() {
+        let mut timings = get_mock_timings();
+        let mut z3 = timings.data[0].clone();
+        z3.variant = Some("z3".into());
+        let mut linalg = timings.data[0].clone();
+        linalg.variant = Some("linalg".into());
+        linalg.part_1_nanos = Some(1e6);
+        timings.data[0] = z3;
+        timings.data.push(linalg);
+
+        let primary = primary_timings(timings.clone(), &HashMap::new());
+        assert_eq!(primary.data.len(), 3);
+        assert_eq!(primary.data[0].variant.as_deref(), Some("z3"));
+
+        let configured = HashMap::from([(crate::day!(1), "linalg".to_string())]);
+        let primary = primary_timings(timings, &configured);
+        assert_eq!(primary.data.len(), 3);
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, primary, &HashMap::new(), 190.0).unwrap();
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01_linalg.rs) | `1.0ms` | `20.0ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn parses_table_options() {
+        let (year, options) =
+            parse_marker("<!--- benchmarking table 2024 columns=total,variant sort=time --->")
+                .unwrap();
+        assert_eq!(year, Some(2024));
+        assert_eq!(options.columns, vec![Column::Total, Column::Variant]);
+        assert_eq!(options.sort, Sort::Time);
+
+        assert_eq!(
+            parse_marker(MARKER).unwrap(),
+            (None, TableOptions::default())
+        );
+        assert_eq!(
+            parse_marker("<!--- benchmarking table 2024 columns=nope --->").is_err(),
+            true
+        );
+        assert_eq!(
+            parse_marker("<!--- benchmarking table 2024 fancy --->").is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn updates_every_table_of_year() {
+        let mut timings = get_mock_timings();
+        timings.year = Some(2024);
+        let mut z3 = timings.data[0].clone();
+        z3.variant = Some("z3".into());
+        timings.data.push(z3);
+
+        let plain = "<!--- benchmarking table 2024 --->";
+        let rich = "<!--- benchmarking table 2024 columns=variant,total,share,samples,allocs sort=time --->";
+        let other = "<!--- benchmarking table 2023 --->";
+        let mut s = format!("{plain}{plain}\n{rich}\n{other}{other}\n");
+        update_content(&mut s, timings, &HashMap::new(), 190.0).unwrap();
+
+        assert_eq!(s.matches(plain).count(), 2);
+        assert_eq!(s.matches(rich).count(), 2);
+        assert_eq!(s.matches(other).count(), 2);
+        assert_eq!(s.matches("_z3.rs").count(), 1);
+        assert_eq!(
+            s.contains("| Day | Variant | Part 1 | Part 2 | Total | Share | Samples | Allocs |"),
+            true
+        );
+        // sorted by time, the slowest day first.
+        assert_eq!(
+            s.contains("| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `50.0ms` | `90.0ms` | `40.9%` | `- / -` | `-` |\n| [Day 2]"),
+            true
+        );
+        // the variant table totals every variant it lists, the others only the primary ones.
+        assert_eq!(s.matches("**Total: 220.00ms**").count(), 1);
+        assert_eq!(s.matches("**Total: 190.00ms**").count(), 1);
+    }
+
+    #[test]
+    fn links_chart_of_table() {
+        let mut timings = get_mock_timings();
+        timings.year = Some(2024);
+        timings.data[0].part_1_stats = Some(Stats {
+            samples: 10,
+            outliers: 0,
+            min_nanos: 1e7,
+            median_nanos: 1e7,
+            mean_nanos: 1e7,
+            p95_nanos: 1e7,
+            stddev_nanos: 0.0,
+        });
+
+        let marker = "<!--- benchmarking table 2024 chart=data/2024/chart.svg --->";
+        let mut s = format!("{marker}{marker}");
+        let charts = update_content(&mut s, timings, &HashMap::new(), 190.0).unwrap();
+
+        assert_eq!(charts.len(), 1);
+        assert_eq!(charts[0].0, "data/2024/chart.svg");
+        assert_eq!(
+            charts[0].1.contains("<title>Day 01 Part 1: 10.0ms</title>"),
+            true
+        );
+        assert_eq!(
+            s.ends_with(&format!(
+                "![2024 Benchmarks](data/2024/chart.svg)\n{marker}"
+            )),
+            true
+        );
+        assert_eq!(s.matches(marker).count(), 2);
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), &HashMap::new(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +791,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
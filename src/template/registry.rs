@@ -0,0 +1,138 @@
+/// Registry of the solutions of the active year, so that a single binary can run and benchmark any set of days
+/// in-process. The build script generates the registry from `src/bin`, the `registry` binary (`src/registry.rs`)
+/// includes it. The per-day binaries stay thin wrappers around the same `run_parts` functions for `cargo solve`.
+use std::{
+    fs,
+    process::{self, Command},
+};
+
+use crate::template::{
+    budget::Budget,
+    commands::{all, time},
+    report::{collect_reports, Report},
+    year, Day,
+};
+
+/// Name of the binary that holds the registry.
+const REGISTRY_BIN: &str = "registry";
+
+/// A registered solution.
+pub struct Entry {
+    pub day: Day,
+    pub variant: Option<&'static str>,
+    /// The parts the solution runs.
+    pub parts: &'static [u8],
+    /// Runs the given parts against an input. Returns `false` if the parse stage panicked.
+    pub solve: fn(&str, &[u8]) -> bool,
+}
+
+/// How `all` and `time` run solutions.
+#[derive(Clone, Copy)]
+pub enum Runner {
+    /// Each solution runs in a child process, `jobs` at a time for untimed runs.
+    Processes { jobs: usize },
+    /// The run is delegated to the registry binary.
+    Registry,
+    /// Solutions run in the current process, which is the registry binary.
+    InProcess(&'static [Entry]),
+}
+
+impl Runner {
+    /// Child processes, `jobs` at a time. Defaults to one job per available core.
+    pub fn processes(jobs: Option<usize>) -> Self {
+        Runner::Processes {
+            jobs: jobs.unwrap_or_else(|| {
+                std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+            }),
+        }
+    }
+}
+
+/// Runs the parts of a registered solution against the input of its day and returns the records it emits.
+/// A missing input is reported and yields no records, like a solution binary that crashes on it.
+pub(crate) fn run(entry: &Entry) -> Vec<Report> {
+    let path = year::data_dir()
+        .join("inputs")
+        .join(format!("{}.txt", entry.day));
+
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("Failed to read input \"{}\": {e}", path.display());
+            return vec![];
+        }
+    };
+
+    collect_reports(|| {
+        (entry.solve)(&input, entry.parts);
+    })
+}
+
+/// Runs `all` or `time` in the registry binary, built for the same profile and features. Exits with its status.
+pub fn delegate(is_release: bool, metrics: bool, budget: Budget, args: &[String]) -> ! {
+    // NOTE: the watchdog of a budget exits the process that runs the solution, that needs one process per solution.
+    if !budget.is_unlimited() {
+        eprintln!("Budgets cannot be enforced in-process, run without --in-process to use them.");
+        process::exit(1);
+    }
+
+    if year::try_current() != year::active() {
+        eprintln!("Only solutions of the active year are registered, run without --in-process.");
+        process::exit(1);
+    }
+
+    let mut command = Command::new("cargo");
+    command.args(["run", "--quiet", "--bin", REGISTRY_BIN]);
+
+    if is_release {
+        command.arg("--release");
+    }
+
+    if metrics {
+        command.args(["--features", "metrics"]);
+    }
+
+    let status = command.arg("--").args(args).status().unwrap_or_else(|e| {
+        eprintln!("Failed to run the registry: {e}");
+        process::exit(1);
+    });
+
+    process::exit(status.code().unwrap_or(1));
+}
+
+/// Entry point of the registry binary: `registry` runs all days, `registry --time [--all] [--store]
+/// [--compare --threshold <percent>] [DAY]` benches them.
+pub fn main(registry: &'static [Entry]) {
+    let mut args = pico_args::Arguments::from_env();
+    let runner = Runner::InProcess(registry);
+    let metrics = cfg!(feature = "metrics");
+
+    // NOTE: `--time` stays in the arguments of the process, the runner benches solutions if it is present.
+    if args.contains("--time") {
+        let run_all = args.contains("--all");
+        let store = args.contains("--store");
+        let compare = args.contains("--compare");
+
+        let (threshold, day) = args
+            .opt_value_from_str("--threshold")
+            .and_then(|threshold| Ok((threshold, args.opt_free_from_str::<Day>()?)))
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            });
+
+        let compare = compare.then(|| threshold.unwrap_or(time::DEFAULT_THRESHOLD));
+
+        time::handle(
+            day,
+            run_all,
+            store,
+            compare,
+            metrics,
+            Budget::default(),
+            runner,
+        );
+    } else {
+        all::handle(!cfg!(debug_assertions), metrics, Budget::default(), runner);
+    }
+}
@@ -0,0 +1,376 @@
+/// Machine-readable result records emitted by solution binaries when run with `--json`.
+/// Every stage of a solution (the optional parse stage and each part) is written as one JSON line to stdout,
+/// human-readable output moves to stderr in that mode.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    str::FromStr,
+    sync::{Mutex, PoisonError},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{metrics::Metrics, stats::Stats, Day};
+
+pub const JSON_FLAG: &str = "--json";
+
+/// Name of the parse stage, parts are named `part_1` and `part_2`.
+pub const PARSE_STAGE: &str = "parse";
+
+/// Outcome of running a single stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The stage produced an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The stage exceeded its wall-clock budget.
+    Timeout,
+    /// The stage exceeded its memory budget.
+    OutOfMemory,
+    /// The stage panicked.
+    Panicked,
+    /// The solution could not be built or started, or exited without reporting its stages.
+    Failed,
+}
+
+impl Status {
+    /// Returns `true` if the stage was aborted for exceeding its budget.
+    pub fn is_over_budget(self) -> bool {
+        matches!(self, Status::Timeout | Status::OutOfMemory)
+    }
+
+    /// Returns `true` if the stage did not run to completion.
+    pub fn is_failure(self) -> bool {
+        self.is_over_budget() || matches!(self, Status::Panicked | Status::Failed)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "out_of_memory",
+            Status::Panicked => "panicked",
+            Status::Failed => "failed",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "timeout" => Ok(Status::Timeout),
+            "out_of_memory" => Ok(Status::OutOfMemory),
+            "panicked" => Ok(Status::Panicked),
+            "failed" => Ok(Status::Failed),
+            x => Err(format!("Unknown status `{x}`.")),
+        }
+    }
+}
+
+/// The result of one stage, i.e. `parse`, `part_1` or `part_2`.
+/// `message` explains failures, e.g. the panic message and location.
+/// `metrics` are only present if the solution was built with the `metrics` feature.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: Day,
+    pub stage: String,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub message: Option<String>,
+    pub stats: Stats,
+    pub metrics: Option<Metrics>,
+}
+
+impl Report {
+    /// Name of the stage that runs part `part`.
+    pub fn part_stage(part: u8) -> String {
+        format!("part_{part}")
+    }
+
+    /// Creates the report of a solution part.
+    pub fn part<T: Display>(day: Day, part: u8, result: Option<&T>, stats: Stats) -> Self {
+        Report {
+            day,
+            stage: Report::part_stage(part),
+            status: if result.is_some() {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            },
+            answer: result.map(ToString::to_string),
+            message: None,
+            stats,
+            metrics: None,
+        }
+    }
+
+    /// Creates the report of a parse stage.
+    pub fn parse(day: Day, stats: Stats) -> Self {
+        Report {
+            day,
+            stage: PARSE_STAGE.into(),
+            status: Status::Solved,
+            answer: None,
+            message: None,
+            stats,
+            metrics: None,
+        }
+    }
+
+    /// Creates the report of a stage that did not run to completion.
+    pub fn failed(day: Day, stage: &str, status: Status, message: String, stats: Stats) -> Self {
+        Report {
+            day,
+            stage: stage.into(),
+            status,
+            answer: None,
+            message: Some(message),
+            stats,
+            metrics: None,
+        }
+    }
+
+    /// Attaches the resource metrics of the stage.
+    pub fn with_metrics(self, metrics: Option<Metrics>) -> Self {
+        Report { metrics, ..self }
+    }
+
+    /// Serializes the report to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        // NOTE: `stringify` never fails for values built from `From<&Report>`.
+        JsonValue::from(self).stringify().unwrap()
+    }
+}
+
+/// Returns `true` if the current process should emit JSON records.
+pub fn is_json_mode() -> bool {
+    std::env::args().any(|x| x == JSON_FLAG)
+}
+
+/// Records of solutions that run in-process, see [`collect_reports`].
+static COLLECTED: Mutex<Option<Vec<Report>>> = Mutex::new(None);
+
+/// Runs solution stages in-process and returns the records they emit, instead of writing them to stdout.
+pub fn collect_reports(func: impl FnOnce()) -> Vec<Report> {
+    let collected = || COLLECTED.lock().unwrap_or_else(PoisonError::into_inner);
+
+    *collected() = Some(vec![]);
+    func();
+    collected().take().unwrap_or_default()
+}
+
+/// Write the JSON record of a stage to stdout if running in `--json` mode.
+pub fn emit_report(report: &Report) {
+    if let Some(collected) = COLLECTED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_mut()
+    {
+        collected.push(report.clone());
+        return;
+    }
+
+    if is_json_mode() {
+        println!("{}", report.to_json_line());
+    }
+}
+
+/// Like `print!`, but writes to stderr in `--json` mode so that stdout only carries JSON records.
+macro_rules! human_print {
+    ($($arg:tt)*) => {
+        if $crate::template::report::is_json_mode() {
+            eprint!($($arg)*);
+        } else {
+            print!($($arg)*);
+        }
+    };
+}
+
+/// Like `println!`, but writes to stderr in `--json` mode so that stdout only carries JSON records.
+macro_rules! human_println {
+    ($($arg:tt)*) => {
+        if $crate::template::report::is_json_mode() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use {human_print, human_println};
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Report> for JsonValue {
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("stage".into(), JsonValue::String(value.stage.clone()));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "message".into(),
+            value
+                .message
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.stats.samples as f64),
+        );
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "metrics".into(),
+            value
+                .metrics
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Report {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let stage = json
+            .get("stage")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.stage to be a string.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        // NOTE: optional, records without failures may omit it.
+        let message = json.get("message").and_then(|v| v.get::<String>()).cloned();
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected report to have key `stats`.")
+            .map(Stats::try_from)??;
+
+        // NOTE: optional, only emitted by solutions built with the `metrics` feature.
+        let metrics = json
+            .get("metrics")
+            .filter(|v| !v.is_null())
+            .map(Metrics::try_from)
+            .transpose()?;
+
+        Ok(Report {
+            day,
+            stage: stage.clone(),
+            status,
+            answer: answer.cloned(),
+            message,
+            stats,
+            metrics,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Report, Status};
+    use crate::{
+        day,
+        template::{metrics::Metrics, stats::Stats},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrips_multiline_answers() {
+        let stats = Stats::from_samples(&[Duration::from_micros(5), Duration::from_micros(7)]);
+        let report = Report::part(day!(14), 2, Some(&"#..#\n.##.\n"), stats);
+
+        let line = report.to_json_line();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(Report::try_from(line.as_str()).unwrap(), report);
+    }
+
+    #[test]
+    fn handles_unsolved_parts() {
+        let line = r#"{"day":"03","stage":"part_1","status":"unsolved","answer":null,"samples":1,"stats":{"samples":1,"outliers":0,"min_nanos":10,"median_nanos":10,"mean_nanos":10,"p95_nanos":10,"stddev_nanos":0}}"#;
+        let report = Report::try_from(line).unwrap();
+        assert_eq!(report.day, day!(3));
+        assert_eq!(report.status, Status::Unsolved);
+        assert_eq!(report.answer, None);
+    }
+
+    #[test]
+    fn roundtrips_metrics() {
+        let stats = Stats::from_samples(&[Duration::from_micros(5)]);
+        let metrics = Metrics {
+            allocations: 3,
+            allocated_bytes: 128,
+            peak_heap_bytes: 64,
+            peak_rss_bytes: None,
+            user_cpu_nanos: 5000,
+            sys_cpu_nanos: 0,
+        };
+        let report = Report::part(day!(1), 1, Some(&1), stats).with_metrics(Some(metrics));
+
+        let parsed = Report::try_from(report.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed.metrics, Some(metrics));
+    }
+
+    #[test]
+    fn roundtrips_failures() {
+        let stats = Stats::from_samples(&[Duration::from_micros(5)]);
+        let report = Report::failed(
+            day!(5),
+            "part_1",
+            Status::Panicked,
+            "boom at src/bin/05.rs:3:5".into(),
+            stats,
+        );
+
+        let parsed = Report::try_from(report.to_json_line().as_str()).unwrap();
+        assert_eq!(parsed.status, Status::Panicked);
+        assert_eq!(parsed.message.unwrap(), "boom at src/bin/05.rs:3:5");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_human_output() {
+        Report::try_from("Part 1: 42 (1.0µs)").unwrap();
+    }
+}
@@ -1,106 +1,552 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    answers::{self, Check},
+    budget::Budget,
+    calendar::Calendar,
+    registry::{self, Runner},
+    report::{Report, Status},
+    stats::Stats,
+    variants::{self, Solution},
+    year, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+static INTERRUPTED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
 
-    let mut need_space = false;
+/// Registers a Ctrl-C handler on first use and returns the flag it sets.
+fn interrupted_flag() -> &'static Arc<AtomicBool> {
+    INTERRUPTED.get_or_init(|| {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Err(e) = signal_hook::flag::register(signal_hook::consts::SIGINT, Arc::clone(&flag))
+        {
+            eprintln!("Failed to register Ctrl-C handler: {e}");
+        }
+        flag
+    })
+}
+
+/// Returns `true` if the last call to [`run_multi`] was cancelled with Ctrl-C.
+pub fn was_interrupted() -> bool {
+    interrupted_flag().load(Ordering::Relaxed)
+}
+
+/// Result of running a set of days.
+pub struct MultiRun {
+    /// Timings of the days that ran, only collected for timed runs.
+    pub timings: Option<Timings>,
+    /// Result records of all days that ran.
+    pub reports: Vec<Report>,
+    /// Number of parts whose output does not match the known answer.
+    pub mismatches: usize,
+}
+
+/// Runs a set of days with `runner`, which must not be [`Runner::Registry`].
+/// With child processes, timed runs and runs with a single job run the days one after another with `cargo run`.
+/// Otherwise, the solutions are built once and run on a pool of `jobs` workers. Their output is buffered and printed
+/// in order of the days. In-process runs call the registered solutions one after another.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    with_metrics: bool,
+    budget: Budget,
+    runner: Runner,
+) -> MultiRun {
+    interrupted_flag().store(false, Ordering::Relaxed);
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let plan: Vec<(Day, Vec<Solution>)> = all_days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .map(|day| match runner {
+            Runner::InProcess(entries) => (day, registered(entries, day)),
+            _ => (day, variants::discover(day)),
+        })
+        .collect();
+
+    let mut summary = Summary::default();
+
+    match runner {
+        // NOTE: benchmarks are not run in parallel, they would disturb each other.
+        Runner::Processes { jobs } if !is_timed && jobs > 1 => {
+            run_parallel(&plan, is_release, with_metrics, budget, jobs, &mut summary);
+        }
+        _ => {
+            run_sequential(
+                &plan,
+                is_release,
+                is_timed,
+                with_metrics,
+                budget,
+                runner,
+                &mut summary,
+            );
+        }
+    }
+
+    summary.finish(is_timed)
+}
+
+/// The registered solutions of a day, in the order of [`variants::discover`].
+fn registered(entries: &[registry::Entry], day: Day) -> Vec<Solution> {
+    let mut solutions: Vec<Solution> = entries
+        .iter()
+        .filter(|entry| entry.day == day)
+        .map(|entry| Solution {
+            day,
+            variant: entry.variant.map(String::from),
+        })
+        .collect();
+
+    solutions.sort();
+    solutions
+}
+
+/// Prints the header of a day.
+fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+fn print_interrupted(day: Day) {
+    println!("\n{ANSI_BOLD}Interrupted.{ANSI_RESET} Skipping day {day} and all following days.");
+}
+
+fn run_sequential(
+    plan: &[(Day, Vec<Solution>)],
+    is_release: bool,
+    is_timed: bool,
+    with_metrics: bool,
+    budget: Budget,
+    runner: Runner,
+    summary: &mut Summary,
+) {
+    for (i, (day, solutions)) in plan.iter().enumerate() {
+        print_day_header(*day, i > 0);
+
+        // skip command invocation for days that have not been scaffolded yet.
+        if solutions.is_empty() {
+            println!("Not solved.");
+            continue;
+        }
+
+        let mut day_timings: Vec<Timing> = vec![];
+
+        for solution in solutions {
+            if solution.variant.is_some() {
+                println!("{ANSI_ITALIC}{}{ANSI_RESET}", solution.label());
             }
-            need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            let result = match runner {
+                Runner::InProcess(entries) => run_registered(entries, solution),
+                _ => child_commands::run_solution(
+                    solution,
+                    is_timed,
+                    is_release,
+                    with_metrics,
+                    budget,
+                ),
+            };
+
+            let reports = match result {
+                Ok(reports) => reports,
+                Err(Error::Interrupted) => {
+                    print_interrupted(*day);
+                    return;
+                }
+                Err(e) => {
+                    println!("✖ {e}");
+                    failed_reports(solution, &e)
+                }
+            };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            day_timings.extend(summary.record(solution, reports));
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        if is_timed && day_timings.len() > 1 {
+            println!("\n{ANSI_BOLD}Variants:{ANSI_RESET}");
+            format_comparison(&day_timings)
+                .iter()
+                .for_each(|x| println!("  {x}"));
+        }
+
+        summary.timings.extend(day_timings);
+    }
+}
+
+/// Runs a registered solution in-process. A running solution cannot be stopped, Ctrl-C takes effect once it returns.
+fn run_registered(entries: &[registry::Entry], solution: &Solution) -> Result<Vec<Report>, Error> {
+    let entry = entries
+        .iter()
+        .find(|e| e.day == solution.day && e.variant == solution.variant.as_deref())
+        .expect("solution is registered");
+
+    let reports = registry::run(entry);
+
+    if was_interrupted() {
+        return Err(Error::Interrupted);
+    }
+
+    Ok(reports)
+}
+
+fn run_parallel(
+    plan: &[(Day, Vec<Solution>)],
+    is_release: bool,
+    with_metrics: bool,
+    budget: Budget,
+    jobs: usize,
+    summary: &mut Summary,
+) {
+    let solutions: Vec<&Solution> = plan.iter().flat_map(|(_, s)| s).collect();
+
+    let executables = match child_commands::build(&solutions, is_release, with_metrics) {
+        Ok(executables) => executables.into_iter().map(Some).collect(),
+        Err(Error::Interrupted) => {
+            println!("\n{ANSI_BOLD}Interrupted.{ANSI_RESET} Skipping all days.");
+            return;
+        }
+        // NOTE: one solution that does not compile fails the whole build, build them one by one to find it.
+        Err(_) => {
+            let mut executables = vec![];
+            for solution in &solutions {
+                match child_commands::build(&[solution], is_release, with_metrics) {
+                    Ok(mut executable) => executables.push(executable.pop()),
+                    Err(Error::Interrupted) => {
+                        println!("\n{ANSI_BOLD}Interrupted.{ANSI_RESET} Skipping all days.");
+                        return;
+                    }
+                    Err(_) => executables.push(None),
+                }
             }
-        });
+            executables
+        }
+    };
+
+    let results = child_commands::run_pool(executables, budget, jobs);
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+    // results arrive in order of completion, hold them back until all previous solutions are printed.
+    let mut pending: HashMap<usize, child_commands::Output> = HashMap::new();
+    let mut next = 0;
+
+    for (i, (day, solutions)) in plan.iter().enumerate() {
+        print_day_header(*day, i > 0);
+
+        if solutions.is_empty() {
+            println!("Not solved.");
+            continue;
+        }
+
+        for solution in solutions {
+            let output = loop {
+                if let Some(output) = pending.remove(&next) {
+                    break Some(output);
+                }
+                match results.recv() {
+                    Ok((index, output)) => {
+                        pending.insert(index, output);
+                    }
+                    Err(_) => break None,
+                }
+            };
+            next += 1;
+
+            if solution.variant.is_some() {
+                println!("{ANSI_ITALIC}{}{ANSI_RESET}", solution.label());
+            }
+
+            let output = output.unwrap_or(child_commands::Output {
+                lines: vec![],
+                result: Err(Error::WorkerExited),
+            });
+
+            output.lines.iter().for_each(|line| println!("{line}"));
+
+            let reports = match output.result {
+                Ok(reports) => reports,
+                Err(Error::Interrupted) => {
+                    print_interrupted(*day);
+                    return;
+                }
+                Err(e) => {
+                    println!("✖ {e}");
+                    failed_reports(solution, &e)
+                }
+            };
+
+            let timing = summary.record(solution, reports);
+            summary.timings.extend(timing);
+        }
+    }
+}
+
+/// Collects the results of the solutions that ran.
+#[derive(Default)]
+struct Summary {
+    timings: Vec<Timing>,
+    reports: Vec<Report>,
+    failures: Vec<String>,
+    mismatches: Vec<String>,
+}
+
+impl Summary {
+    /// Records the result records of a solution and prints its answer checks. Returns its timing, if it ran.
+    fn record(&mut self, solution: &Solution, reports: Vec<Report>) -> Option<Timing> {
+        if reports.is_empty() {
+            println!("Not solved.");
+            return None;
+        }
+
+        reports
+            .iter()
+            .filter(|r| r.status.is_failure())
+            .for_each(|r| self.failures.push(format_failure(solution, r)));
+
+        let checks = answers::check_reports(&reports);
+
+        // NOTE: stay quiet for days without known answers.
+        if checks.iter().any(|(_, c)| *c != Check::Unknown) {
+            let formatted: Vec<String> = checks.iter().map(|(p, c)| c.format(*p)).collect();
+            println!("Answers: {}", formatted.join(" · "));
+        }
+
+        checks
+            .iter()
+            .filter(|(_, c)| c.is_mismatch())
+            .for_each(|(p, c)| {
+                self.mismatches
+                    .push(format!("{} {}", solution.label(), c.format(*p)));
+            });
+
+        let mut timing = child_commands::timing_from_reports(&reports, solution.day);
+        timing.variant.clone_from(&solution.variant);
+        self.reports.extend(reports);
+
+        Some(timing)
+    }
+
+    /// Prints the summary of failures and mismatches, and the total run time of timed runs.
+    fn finish(self, is_timed: bool) -> MultiRun {
+        if !self.failures.is_empty() {
+            println!("\n{ANSI_BOLD}Failures:{ANSI_RESET}");
+            self.failures.iter().for_each(|x| println!("  {x}"));
+        }
+
+        if !self.mismatches.is_empty() {
+            println!("\n{ANSI_BOLD}Answer mismatches:{ANSI_RESET}");
+            self.mismatches.iter().for_each(|x| println!("  {x}"));
+        }
+
+        let timings = if is_timed {
+            let timings = Timings {
+                year: year::try_current(),
+                data: self.timings,
+            };
+            let total_millis = timings.total_millis();
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+            Some(timings)
+        } else {
+            None
+        };
+
+        MultiRun {
+            timings,
+            reports: self.reports,
+            mismatches: self.mismatches.len(),
+        }
+    }
+}
+
+/// Creates the result records of a solution that could not run, which mark each of its parts as failed.
+fn failed_reports(solution: &Solution, error: &Error) -> Vec<Report> {
+    let has_part_two =
+        year::try_current().is_none_or(|year| Calendar::of(year).has_part_two(solution.day));
+    let parts = if has_part_two { 2 } else { 1 };
+
+    (1..=parts)
+        .map(|part| {
+            Report::failed(
+                solution.day,
+                &Report::part_stage(part),
+                Status::Failed,
+                error.to_string(),
+                Stats::from_samples(&[Duration::ZERO]),
+            )
+        })
+        .collect()
+}
+
+/// Formats a stage that did not run to completion for the summary, e.g. `Day 05 part_1 (panicked): boom at …`.
+fn format_failure(solution: &Solution, report: &Report) -> String {
+    let summary = format!("{} {} ({})", solution.label(), report.stage, report.status);
+    match &report.message {
+        Some(message) => format!("{summary}: {message}"),
+        None => summary,
     }
 }
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed,
+    Interrupted,
+    /// The solution exited unsuccessfully without reporting its stages, e.g. `cargo run` failed to compile it.
+    Exited(Option<i32>),
+    /// The worker running the solution stopped before sending its output.
+    WorkerExited,
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "lost the output of the solution"),
+            Error::BuildFailed => write!(f, "failed to build"),
+            Error::Interrupted => write!(f, "interrupted"),
+            Error::Exited(Some(code)) => write!(f, "exited with code {code}"),
+            Error::Exited(None) => write!(f, "terminated by a signal"),
+            Error::WorkerExited => write!(f, "worker exited"),
+            Error::IO(e) => write!(f, "failed to run: {e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Formats the timings of the variants of a day side by side, the fastest variant is marked.
+fn format_comparison(timings: &[Timing]) -> Vec<String> {
+    let name = |t: &Timing| t.variant.clone().unwrap_or_else(|| "(plain)".into());
+    let width = timings.iter().map(|t| name(t).len()).max().unwrap_or(0);
+    let cell = |x: Option<f64>| {
+        x.map_or_else(
+            || "-".into(),
+            |nanos| format!("{:.1?}", Duration::from_secs_f64(nanos / 1e9)),
+        )
+    };
+
+    let fastest = timings
+        .iter()
+        .filter(|t| t.part_1_nanos.is_some() || t.part_2_nanos.is_some())
+        .min_by(|a, b| a.total_nanos().total_cmp(&b.total_nanos()))
+        .map(name);
+
+    timings
+        .iter()
+        .map(|t| {
+            let line = format!(
+                "{:<width$}  Part 1 {:>10}  Part 2 {:>10}  Total {:.1?}",
+                name(t),
+                cell(t.part_1_nanos),
+                cell(t.part_2_nanos),
+                Duration::from_secs_f64(t.total_nanos() / 1e9)
+            );
+
+            if fastest.as_ref() == Some(&name(t)) {
+                format!("{line}  ◀ fastest")
+            } else {
+                line
+            }
+        })
+        .collect()
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their result records.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        budget::Budget,
+        report::{Report, Status, JSON_FLAG},
+        variants::Solution,
+        Day,
+    };
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
+        mem,
+        path::PathBuf,
         process::{Command, Stdio},
+        str::FromStr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc, Arc, Mutex,
+        },
         thread,
+        time::Duration,
     };
+    use tinyjson::JsonValue;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Arguments passed to a solution binary.
+    fn solution_args(is_timed: bool, budget: Budget) -> Vec<String> {
+        let mut args = vec![JSON_FLAG.to_string()];
+
+        if is_timed {
+            // mirror `--time` flag to child invocations.
+            args.push("--time".into());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        args.extend(budget.to_args());
+        args
+    }
+
+    /// Run the solution bin for a given day and collect the result records it emits.
+    /// Returns [`Error::Interrupted`] if Ctrl-C was pressed while the solution was running, the child is killed then.
+    /// With `with_metrics`, the solution is built with the `metrics` feature and reports resource usage.
+    pub fn run_solution(
+        solution: &Solution,
+        is_timed: bool,
+        is_release: bool,
+        with_metrics: bool,
+        budget: Budget,
+    ) -> Result<Vec<Report>, Error> {
+        let mut command = Command::new("cargo");
+        command.args(["run", "--quiet", "--bin", &solution.bin_name()]);
 
         if is_release {
-            args.push("--release");
+            command.arg("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if with_metrics {
+            command.args(["--features", "metrics"]);
         }
 
+        command.arg("--").args(solution_args(is_timed, budget));
+
+        collect(command, |line| println!("{line}"))
+    }
+
+    /// Spawns `command` and collects the result records it emits. Any other output is passed to `emit` as it arrives.
+    fn collect(
+        mut command: Command,
+        emit: impl Fn(String) + Clone + Send + 'static,
+    ) -> Result<Vec<Report>, Error> {
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // in `--json` mode, stdout carries result records and stderr the human-readable output, which is forwarded.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = command
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,85 +554,227 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let emit_stderr = emit.clone();
+        let stderr_thread = thread::spawn(move || {
+            stderr.lines().map_while(Result::ok).for_each(emit_stderr);
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stdout_thread = thread::spawn(move || {
+            let mut reports = vec![];
+
+            for line in stdout.lines().map_while(Result::ok) {
+                // anything that is not a record was printed by the solution itself.
+                match Report::try_from(line.as_str()) {
+                    Ok(report) => reports.push(report),
+                    Err(_) => emit(line),
+                }
+            }
+
+            reports
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        // NOTE: poll instead of blocking on the child, so that a Ctrl-C can kill solutions that do not exit on SIGINT.
+        let status = loop {
+            if let Some(status) = cmd.try_wait()? {
+                break status;
+            }
+            if super::was_interrupted() {
+                // the output threads are detached, a surviving grandchild may still hold the pipes.
+                cmd.kill()?;
+                cmd.wait()?;
+                return Err(Error::Interrupted);
+            }
+            thread::sleep(Duration::from_millis(20));
+        };
+
+        stderr_thread.join().unwrap();
+        let reports = stdout_thread.join().unwrap();
+
+        if super::was_interrupted() {
+            return Err(Error::Interrupted);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        // a solution that fails a stage still reports it, e.g. a panic or an exceeded budget.
+        if !status.success() && reports.is_empty() {
+            return Err(Error::Exited(status.code()));
+        }
 
-        Ok(output)
+        Ok(reports)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Builds the binaries of `solutions` in one cargo invocation. Returns their executables, in the same order.
+    pub fn build(
+        solutions: &[&Solution],
+        is_release: bool,
+        with_metrics: bool,
+    ) -> Result<Vec<PathBuf>, Error> {
+        if solutions.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut command = Command::new("cargo");
+        command.args([
+            "build",
+            "--quiet",
+            "--message-format=json-render-diagnostics",
+        ]);
+
+        if is_release {
+            command.arg("--release");
+        }
+
+        if with_metrics {
+            command.args(["--features", "metrics"]);
+        }
+
+        for solution in solutions {
+            command.args(["--bin", &solution.bin_name()]);
+        }
+
+        // NOTE: compiler diagnostics are rendered to stderr, which is inherited.
+        let output = command.stderr(Stdio::inherit()).output()?;
 
-        output
+        if super::was_interrupted() {
+            return Err(Error::Interrupted);
+        }
+
+        if !output.status.success() {
+            return Err(Error::BuildFailed);
+        }
+
+        let executables = executables_from_messages(&String::from_utf8_lossy(&output.stdout));
+
+        solutions
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
+            .map(|s| {
+                executables
+                    .get(&s.bin_name())
+                    .cloned()
+                    .ok_or(Error::BuildFailed)
+            })
+            .collect()
+    }
+
+    /// Extracts the executables of the built binaries from the JSON messages of `cargo build`, keyed by binary name.
+    fn executables_from_messages(messages: &str) -> HashMap<String, PathBuf> {
+        messages
+            .lines()
+            .filter_map(|line| {
+                let json = JsonValue::from_str(line).ok()?;
+                let json = json.get::<HashMap<String, JsonValue>>()?;
+
+                if json.get("reason")?.get::<String>()? != "compiler-artifact" {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+                let name = json
+                    .get("target")?
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("name")?
+                    .get::<String>()?;
+                let executable = json.get("executable")?.get::<String>()?;
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((name.clone(), PathBuf::from(executable)))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            .collect()
+    }
 
-                timings.total_nanos += nanos;
+    /// Buffered output and result records of a solution that ran on the worker pool.
+    pub struct Output {
+        pub lines: Vec<String>,
+        pub result: Result<Vec<Report>, Error>,
+    }
+
+    /// Runs the executables of untimed solutions on `jobs` worker threads.
+    /// Sends the index of each executable with its output as soon as it completes.
+    /// Solutions that failed to build have no executable, their output is an error.
+    pub fn run_pool(
+        executables: Vec<Option<PathBuf>>,
+        budget: Budget,
+        jobs: usize,
+    ) -> mpsc::Receiver<(usize, Output)> {
+        let (sender, receiver) = mpsc::channel();
+        let executables = Arc::new(executables);
+        let next = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..jobs.min(executables.len()) {
+            let sender = sender.clone();
+            let executables = Arc::clone(&executables);
+            let next = Arc::clone(&next);
+
+            thread::spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(executable) = executables.get(index) else {
+                    break;
+                };
+
+                let output = match executable {
+                    Some(executable) => {
+                        let mut command = Command::new(executable);
+                        command.args(solution_args(false, budget));
+
+                        let lines = Arc::new(Mutex::new(vec![]));
+                        let sink = Arc::clone(&lines);
+                        let result = collect(command, move |line| sink.lock().unwrap().push(line));
+                        let lines = mem::take(&mut *lines.lock().unwrap());
+                        Output { lines, result }
+                    }
+                    None => Output {
+                        lines: vec![],
+                        result: Err(Error::BuildFailed),
+                    },
+                };
+
+                if sender.send((index, output)).is_err() {
+                    break;
+                }
             });
+        }
 
-        timings
+        receiver
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    pub fn timing_from_reports(reports: &[Report], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day);
+
+        for r in reports.iter().filter(|r| r.day == day) {
+            let status = Some(r.status);
+            match r.stage.as_str() {
+                "parse" => timings.parse_status = status,
+                "part_1" => timings.part_1_status = status,
+                "part_2" => timings.part_2_status = status,
+                _ => {}
+            }
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        reports
+            .iter()
+            .filter(|r| r.day == day && r.status == Status::Solved)
+            .for_each(|r| {
+                let nanos = Some(r.stats.median_nanos);
+                let stats = Some(r.stats);
+
+                match r.stage.as_str() {
+                    "parse" => {
+                        timings.parse_nanos = nanos;
+                        timings.parse_stats = stats;
+                        timings.parse_metrics = r.metrics;
+                    }
+                    "part_1" => {
+                        timings.part_1_nanos = nanos;
+                        timings.part_1_stats = stats;
+                        timings.part_1_metrics = r.metrics;
+                    }
+                    "part_2" => {
+                        timings.part_2_nanos = nanos;
+                        timings.part_2_stats = stats;
+                        timings.part_2_metrics = r.metrics;
+                    }
+                    _ => {}
+                }
+            });
+
+        timings
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,53 +793,149 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{timing_from_reports, Error};
+        use crate::{
+            day,
+            template::{
+                report::{Report, Status},
+                stats::Stats,
+                variants::Solution,
+            },
+        };
+        use std::time::Duration;
 
-        use crate::day;
+        fn stats(nanos: &[u64]) -> Stats {
+            let samples: Vec<_> = nanos.iter().map(|x| Duration::from_nanos(*x)).collect();
+            Stats::from_samples(&samples)
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_timings() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    Report::part(day!(1), 1, Some(&0), stats(&[70, 74, 78])),
+                    Report::part(day!(1), 2, Some(&10), stats(&[74_130_000])),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos(), 74130074_f64);
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 74_f64);
+            assert_approx_eq!(res.part_2_nanos.unwrap(), 74_130_000_f64);
+            assert_eq!(res.part_1_stats.unwrap().samples, 3);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_parse_stage() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    Report::parse(day!(1), stats(&[1_500_000])),
+                    Report::part(day!(1), 1, Some(&"x\ny"), stats(&[500_000])),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos(), 2_000_000_f64);
+            assert_approx_eq!(res.parse_nanos.unwrap(), 1_500_000_f64);
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 500_000_f64);
+            assert_eq!(res.part_2_nanos.is_none(), true);
+        }
+
+        #[test]
+        fn records_failed_parts() {
+            let res = timing_from_reports(
+                &[
+                    Report::failed(
+                        day!(1),
+                        "part_1",
+                        Status::Panicked,
+                        "boom".into(),
+                        stats(&[10]),
+                    ),
+                    Report::part(day!(1), 2, Some(&1), stats(&[20])),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos(), 20_f64);
+            assert_eq!(res.part_1_nanos.is_none(), true);
+            assert_eq!(res.part_1_status, Some(Status::Panicked));
+            assert_eq!(res.part_2_status, Some(Status::Solved));
+        }
+
+        #[test]
+        fn records_solutions_that_could_not_run() {
+            let solution = Solution {
+                day: day!(1),
+                variant: None,
+            };
+            let reports = super::super::failed_reports(&solution, &Error::BuildFailed);
+
+            assert_eq!(reports.len(), 2);
+            assert_eq!(reports[0].message.as_deref(), Some("failed to build"));
+
+            let res = timing_from_reports(&reports, day!(1));
+            assert_eq!(res.part_1_status, Some(Status::Failed));
+            assert_eq!(res.part_2_status, Some(Status::Failed));
+            assert_eq!(res.part_1_nanos.is_none(), true);
+        }
+
+        #[test]
+        fn compares_variants() {
+            let mut linalg = timing_from_reports(
+                &[
+                    Report::part(day!(13), 1, Some(&1), stats(&[2_000_000])),
+                    Report::part(day!(13), 2, Some(&2), stats(&[3_000_000])),
+                ],
+                day!(13),
+            );
+            linalg.variant = Some("linalg".into());
+            let mut z3 = timing_from_reports(
+                &[
+                    Report::part(day!(13), 1, Some(&1), stats(&[20_000_000])),
+                    Report::part(day!(13), 2, Some(&2), stats(&[30_000_000])),
+                ],
+                day!(13),
+            );
+            z3.variant = Some("z3".into());
+
+            let lines = super::super::format_comparison(&[linalg, z3]);
+            assert_eq!(
+                lines[0],
+                "linalg  Part 1      2.0ms  Part 2      3.0ms  Total 5.0ms  ◀ fastest"
+            );
+            assert_eq!(
+                lines[1],
+                "z3      Part 1     20.0ms  Part 2     30.0ms  Total 50.0ms"
+            );
+        }
+
+        #[test]
+        fn reads_executables_from_build_messages() {
+            let messages = [
+                r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code","kind":["lib"]},"executable":null}"#,
+                r#"{"reason":"compiler-artifact","target":{"name":"13_z3","kind":["bin"]},"executable":"/t/debug/13_z3"}"#,
+                r#"{"reason":"build-finished","success":true}"#,
+            ]
+            .join("\n");
+
+            let executables = super::executables_from_messages(&messages);
+            assert_eq!(executables.len(), 1);
+            assert_eq!(
+                executables.get("13_z3").map(|x| x.to_str().unwrap()),
+                Some("/t/debug/13_z3")
+            );
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn skips_missing_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    Report::part::<u32>(day!(1), 1, None, stats(&[10])),
+                    Report::part::<u32>(day!(1), 2, None, stats(&[10])),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_approx_eq!(res.total_nanos(), 0_f64);
+            assert_eq!(res.part_1_nanos.is_none(), true);
+            assert_eq!(res.part_2_nanos.is_none(), true);
         }
     }
 }
@@ -1,96 +1,268 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::Cell;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, Once};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers;
+use crate::template::aoc_client::{AocClientError, Client};
+use crate::template::budget::guarded;
+use crate::template::metrics::{measure, Metrics};
+use crate::template::report::{
+    emit_report, human_print, human_println, Report, Status, PARSE_STAGE,
+};
+use crate::template::stats::Stats;
+use crate::template::submissions::{self, Submission, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, ANSI_ITALIC, ANSI_RESET, INPUT_FLAG};
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let outcome = run_timed(
+        || {
+            guarded(day, &Report::part_stage(part), &part_str, || {
+                func(input.clone())
+            })
+        },
+        || input.clone(),
+        &func,
+        |result| print_result(result, &part_str, ""),
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    finish_part(outcome, day, part, &part_str);
+}
+
+/// Run a solution part that borrows the output of a parse stage.
+/// The part runs once against the shared `parsed` value. Bench iterations run against the output of a fresh `parse`
+/// of the input each, which is not timed, so a part never benefits from results cached by itself or another part.
+pub fn run_parsed_part<P, T: Display>(
+    func: impl Fn(&P) -> Option<T>,
+    parsed: &P,
+    parse: impl Fn(&str) -> P,
+    input: &str,
+    day: Day,
+    part: u8,
+) {
+    let part_str = format!("Part {part}");
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    let outcome = run_timed(
+        || guarded(day, &Report::part_stage(part), &part_str, || func(parsed)),
+        || parse(input),
+        // NOTE: hand the input back so that it is dropped outside of the timed section.
+        |input| (func(&input), input),
+        |result| print_result(result, &part_str, ""),
+    );
+
+    finish_part(outcome, day, part, &part_str);
+}
+
+/// Run the parse stage of a solution and return its output, which is then shared by both parts.
+/// If parsing panics, neither part can run and `None` is returned.
+pub fn run_parse<P>(parse: impl Fn(&str) -> P, input: &str, day: Day) -> Option<P> {
+    let outcome = run_timed(
+        || guarded(day, PARSE_STAGE, "Parse", || parse(input)),
+        || input,
+        &parse,
+        |_| human_print!("Parse:"),
+    );
+
+    match outcome {
+        Ok((parsed, stats, metrics)) => {
+            human_print!("\r");
+            human_println!("Parse:{}", format_duration(&stats));
+            print_metrics(metrics.as_ref());
+            emit_report(&Report::parse(day, stats).with_metrics(metrics));
+            Some(parsed)
+        }
+        Err(panic) => {
+            report_panic(&panic, day, PARSE_STAGE, "Parse");
+            None
+        }
     }
 }
 
+fn finish_part<T: Display>(
+    outcome: Result<(Option<T>, Stats, Option<Metrics>), CapturedPanic>,
+    day: Day,
+    part: u8,
+    part_str: &str,
+) {
+    match outcome {
+        Ok((result, stats, metrics)) => {
+            print_result(&result, part_str, &format_duration(&stats));
+            print_metrics(metrics.as_ref());
+            emit_report(&Report::part(day, part, result.as_ref(), stats).with_metrics(metrics));
+
+            submit_result(result, day, part);
+        }
+        Err(panic) => report_panic(&panic, day, &Report::part_stage(part), part_str),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Exit code of a solution binary whose parse stage panicked.
+pub const PANIC_EXIT_CODE: i32 = 101;
+
+/// A panic raised by a solution stage, captured so that the following stages still run.
+struct CapturedPanic {
+    message: String,
+    elapsed: Duration,
+}
+
+thread_local! {
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+}
+
+static CAPTURED: Mutex<Option<String>> = Mutex::new(None);
+
+/// Runs `func`, catching a panic and its location instead of letting it terminate the process.
+/// Panics outside of a capturing section are still reported by the default hook.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CAPTURING.with(Cell::get) {
+                default_hook(info);
+                return;
+            }
+
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Box<dyn Any>".into());
+
+            let message = match info.location() {
+                Some(location) => format!("{message} at {location}"),
+                None => message,
+            };
+
+            *CAPTURED.lock().unwrap_or_else(|e| e.into_inner()) = Some(message);
+        }));
+    });
+
+    CAPTURING.with(|x| x.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CAPTURING.with(|x| x.set(false));
+
+    result.map_err(|_| {
+        CAPTURED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+fn report_panic(panic: &CapturedPanic, day: Day, stage: &str, label: &str) {
+    human_print!("\r");
+    human_println!("{label}: ✖ panicked: {}", panic.message);
+
+    emit_report(&Report::failed(
+        day,
+        stage,
+        Status::Panicked,
+        panic.message.clone(),
+        Stats::from_samples(&[panic.elapsed]),
+    ));
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
+///
+/// Panics of the first run are captured and returned as an error, the part is not benched then.
+/// Resource metrics are collected for the first run only.
+fn run_timed<T, S, R>(
+    first: impl FnOnce() -> T,
+    setup: impl Fn() -> S,
+    run: impl Fn(S) -> R,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+) -> Result<(T, Stats, Option<Metrics>), CapturedPanic> {
+    let started = Instant::now();
+    let result = catch_panic(|| {
+        // NOTE: time inside of `measure`, so that collecting metrics does not count towards the duration.
+        measure(|| {
+            let timer = Instant::now();
+            let result = first();
+            (result, timer.elapsed())
+        })
+    });
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
-    };
-    let base_time = timer.elapsed();
+    let ((result, base_time), metrics) = result.map_err(|message| CapturedPanic {
+        message,
+        elapsed: started.elapsed(),
+    })?;
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if std::env::args().any(|x| x == "--time") {
+        bench(setup, run, &base_time)
     } else {
-        (base_time, 1)
+        Stats::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    Ok((result, stats, metrics))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Bench a solution part. A short warmup (approx. 100ms or at least one run) precedes the measured iterations,
+/// so that cold caches and lazy initialization do not skew the samples.
+/// `setup` prepares the input of each iteration outside of the timed section.
+fn bench<S, R>(setup: impl Fn() -> S, run: impl Fn(S) -> R, base_time: &Duration) -> Stats {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    human_print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let base_nanos = cmp::max(base_time.as_nanos(), 10);
+    let warmup_iterations = (Duration::from_millis(100).as_nanos() / base_nanos).clamp(1, 1000);
+    let bench_iterations = (Duration::from_secs(1).as_nanos() / base_nanos).clamp(10, 10000);
+
+    for _ in 0..warmup_iterations {
+        black_box(run(black_box(setup())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
+        let input = setup();
         let timer = Instant::now();
-        black_box(func(black_box(cloned)));
+        let output = black_box(run(black_box(input)));
         timers.push(timer.elapsed());
+        drop(output);
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+fn format_duration(stats: &Stats) -> String {
+    let duration = stats.median();
+    let samples = stats.samples;
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({duration:.1?} @ {samples} samples) {}",
+            stats.format_spread()
+        )
+    }
+}
+
+fn print_metrics(metrics: Option<&Metrics>) {
+    if let Some(metrics) = metrics {
+        human_println!("  {ANSI_ITALIC}{}{ANSI_RESET}", metrics.format());
     }
 }
 
@@ -102,41 +274,43 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    human_print!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    human_print!("\r");
+                    human_println!("{str}");
+                    human_println!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    human_print!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    human_print!("\r");
+                    human_println!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                human_print!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                human_print!("\r");
+                human_println!("{part}: ✖             ");
             }
         }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  1. a session cookie is configured, see `aoc_client::Config::from_env`.
+///  2. the answer passes the checks against the submission log and is confirmed (or `--yes` is passed).
+///
+/// The confirmation is read from stdin, so an input read from stdin (`--input -`) requires `--yes`.
 fn submit_result<T: Display>(
-    result: T,
+    result: Option<T>,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Verdict, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +333,74 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let Some(result) = result else {
+        eprintln!("Refusing to submit: part {part} did not return an answer.");
+        return None;
+    };
+
+    let answer = result.to_string();
+
+    let log = submissions::read_log(day, part).unwrap_or_else(|e| {
+        eprintln!("Failed to read the submission log: {e}");
         process::exit(1);
+    });
+
+    if let Err(reason) = submissions::vet(&log, &answer) {
+        eprintln!("Refusing to submit: {reason}");
+        return None;
+    }
+
+    let is_confirmed = args.contains(&"--yes".into());
+    let is_stdin_input = args.windows(2).any(|x| x[0] == INPUT_FLAG && x[1] == "-");
+
+    if !is_confirmed && is_stdin_input {
+        eprintln!("Refusing to submit: the input was read from stdin, which is needed to confirm. Pass `--yes`.");
+        return None;
+    }
+
+    if !is_confirmed && !confirm(&format!("Submit `{answer}` for part {part}?")) {
+        human_println!("Not submitted.");
+        return None;
+    }
+
+    let client = Client::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to set up the Advent of Code client: {e}");
+        process::exit(1);
+    });
+
+    human_println!("Submitting result...");
+    let response = client.submit(day, part, &answer);
+
+    match &response {
+        Ok(verdict) => human_println!("{}", verdict.describe()),
+        Err(e) => eprintln!("Failed to submit: {e}"),
+    }
+
+    if let Ok(verdict) = response {
+        if let Err(e) = submissions::append(day, part, &Submission::new(&answer, verdict)) {
+            eprintln!("Failed to log the submission: {e}");
+        }
+
+        if verdict == Verdict::Correct {
+            match answers::store(day, part, &answer) {
+                Ok(()) => human_println!("Recorded the known answer of part {part}."),
+                Err(e) => eprintln!("Failed to record the known answer of part {part}: {e}"),
+            }
+        }
+    }
+
+    Some(response)
+}
+
+/// Asks for confirmation on stderr, anything but `y` / `yes` declines.
+pub(crate) fn confirm(prompt: &str) -> bool {
+    eprint!("{prompt} [y/N] ");
+    let _ = std::io::stderr().flush();
+
+    let mut line = String::new();
+    if std::io::stdin().read_line(&mut line).is_err() {
+        return false;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    matches!(line.trim().to_lowercase().as_str(), "y" | "yes")
 }
@@ -0,0 +1,191 @@
+/// Summary statistics over a set of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Distribution of the execution time of a solution part.
+/// Outliers are rejected with Tukey's fences (1.5 × IQR) before the statistics are computed,
+/// `samples` and `outliers` still count every measured run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u64,
+    pub outliers: u64,
+    pub min_nanos: f64,
+    pub median_nanos: f64,
+    pub mean_nanos: f64,
+    pub p95_nanos: f64,
+    pub stddev_nanos: f64,
+}
+
+impl Stats {
+    /// Computes the statistics for a non-empty set of samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x >= lower && *x <= upper)
+            .collect();
+
+        let mean = kept.iter().sum::<f64>() / kept.len() as f64;
+        let variance = kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / kept.len() as f64;
+
+        Stats {
+            samples: sorted.len() as u64,
+            outliers: (sorted.len() - kept.len()) as u64,
+            min_nanos: kept[0],
+            median_nanos: percentile(&kept, 0.5),
+            mean_nanos: mean,
+            p95_nanos: percentile(&kept, 0.95),
+            stddev_nanos: variance.sqrt(),
+        }
+    }
+
+    /// The headline duration of a benchmark, i.e. the median.
+    pub fn median(&self) -> Duration {
+        to_duration(self.median_nanos)
+    }
+
+    /// The standard deviation of the samples after outlier rejection.
+    pub fn stddev(&self) -> Duration {
+        to_duration(self.stddev_nanos)
+    }
+
+    /// Formats the spread of the distribution, e.g. `[min 1.0µs · mean 1.2µs · p95 1.9µs · σ 0.2µs]`.
+    pub fn format_spread(&self) -> String {
+        let mut parts = vec![
+            format!("min {:.1?}", to_duration(self.min_nanos)),
+            format!("mean {:.1?}", to_duration(self.mean_nanos)),
+            format!("p95 {:.1?}", to_duration(self.p95_nanos)),
+            format!("σ {:.1?}", self.stddev()),
+        ];
+
+        if self.outliers > 0 {
+            parts.push(format!("{} outliers", self.outliers));
+        }
+
+        format!("[{}]", parts.join(" · "))
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Linearly interpolated percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Stats {
+            samples: number("samples")? as u64,
+            outliers: number("outliers")? as u64,
+            min_nanos: number("min_nanos")?,
+            median_nanos: number("median_nanos")?,
+            mean_nanos: number("mean_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+            stddev_nanos: number("stddev_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_distribution() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40, 50]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min_nanos, 10.0);
+        assert_eq!(stats.median_nanos, 30.0);
+        assert_eq!(stats.mean_nanos, 30.0);
+        assert_eq!(stats.p95_nanos, 48.0);
+        assert_eq!(stats.stddev_nanos, 200_f64.sqrt());
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median_nanos, 100.0);
+        assert_eq!(stats.p95_nanos < 200.0, true);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median(), Duration::from_nanos(42));
+        assert_eq!(stats.stddev_nanos, 0.0);
+    }
+
+    #[test]
+    fn formats_spread() {
+        let stats = Stats::from_samples(&nanos(&[1000, 1000, 1000, 1000, 9000]));
+        assert_eq!(
+            stats.format_spread(),
+            "[min 1.0µs · mean 1.0µs · p95 1.0µs · σ 0.0ns · 1 outliers]"
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40, 50]));
+        let json = JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json).unwrap(), stats);
+    }
+}
@@ -0,0 +1,348 @@
+/// Local log of submitted answers, kept as `data/{year}/submissions/{day}-{part}.jsonl`.
+/// Before an answer is submitted, the log is consulted to refuse answers that are known to be wrong or that
+/// lie outside of the bounds the site already hinted at ("too high" / "too low").
+use std::{
+    collections::HashMap,
+    env, fmt, fs,
+    io::{self, ErrorKind, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{year, Day};
+
+/// The response of the site to a submission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint.
+    Wrong,
+    /// Submitted too recently after a previous answer, the answer was not checked.
+    Throttled,
+    /// The part is not open or already solved, the answer was not checked.
+    WrongLevel,
+    /// The response could not be interpreted.
+    Unknown,
+}
+
+impl Verdict {
+    /// Interprets the message in the HTTP response body of a submission, i.e. its `<article>` as converted by
+    /// `Client::submit`.
+    pub fn from_response(response: &str) -> Self {
+        let response = response.to_lowercase();
+
+        if response.contains("that's the right answer") {
+            Verdict::Correct
+        } else if response.contains("too high") {
+            Verdict::TooHigh
+        } else if response.contains("too low") {
+            Verdict::TooLow
+        } else if response.contains("not the right answer") {
+            Verdict::Wrong
+        } else if response.contains("answer too recently") {
+            Verdict::Throttled
+        } else if response.contains("solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// A human-readable summary of the response.
+    pub fn describe(self) -> &'static str {
+        match self {
+            Verdict::Correct => "That's the right answer!",
+            Verdict::TooHigh => "That's not the right answer, your answer is too high.",
+            Verdict::TooLow => "That's not the right answer, your answer is too low.",
+            Verdict::Wrong => "That's not the right answer.",
+            Verdict::Throttled => "You gave an answer too recently, wait before trying again.",
+            Verdict::WrongLevel => "The part is not open or was already solved.",
+            Verdict::Unknown => "The response could not be interpreted.",
+        }
+    }
+
+    /// Returns `true` if the site rejected the answer.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::Throttled => "throttled",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "throttled" => Ok(Verdict::Throttled),
+            "wrong_level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            x => Err(format!("Unknown verdict `{x}`.")),
+        }
+    }
+}
+
+/// A submitted answer. `timestamp` is in seconds since the unix epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub answer: String,
+    pub timestamp: u64,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    pub fn new(answer: &str, verdict: Verdict) -> Self {
+        Submission {
+            answer: answer.into(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |x| x.as_secs()),
+            verdict,
+        }
+    }
+}
+
+/// Checks whether `answer` may be submitted given the previous submissions of the part.
+/// Returns the reason for refusing otherwise.
+pub fn vet(log: &[Submission], answer: &str) -> Result<(), String> {
+    if answer.trim().is_empty() {
+        return Err("the answer is empty.".into());
+    }
+
+    if answer.contains('\n') {
+        return Err("the answer spans multiple lines.".into());
+    }
+
+    if log.iter().any(|x| x.verdict == Verdict::Correct) {
+        return Err("the part was already solved.".into());
+    }
+
+    if let Some(previous) = log
+        .iter()
+        .find(|x| x.answer == answer && x.verdict.is_wrong())
+    {
+        return Err(format!(
+            "`{answer}` was already submitted and is {}.",
+            previous.verdict.to_string().replace('_', " ")
+        ));
+    }
+
+    if let Ok(value) = answer.parse::<i128>() {
+        let bound = |verdict: Verdict| {
+            log.iter()
+                .filter(move |x| x.verdict == verdict)
+                .filter_map(|x| x.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(Verdict::TooHigh).min() {
+            if value >= high {
+                return Err(format!(
+                    "`{answer}` is not below {high}, which is too high."
+                ));
+            }
+        }
+
+        if let Some(low) = bound(Verdict::TooLow).max() {
+            if value <= low {
+                return Err(format!("`{answer}` is not above {low}, which is too low."));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn log_path(day: Day, part: u8) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join(year::data_dir())
+        .join("submissions")
+        .join(format!("{day}-{part}.jsonl"))
+}
+
+/// Reads the submissions of a part. A missing log is empty.
+pub fn read_log(day: Day, part: u8) -> Result<Vec<Submission>, String> {
+    let path = log_path(day, part);
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("{}: {e}", path.display())),
+    };
+
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(Submission::try_from)
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Appends a submission to the log of a part.
+pub fn append(day: Day, part: u8, submission: &Submission) -> io::Result<()> {
+    let path = log_path(day, part);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // NOTE: `stringify` never fails for values built from `From<&Submission>`.
+    let line = JsonValue::from(submission).stringify().unwrap();
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{line}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            answer: answer.clone(),
+            timestamp: *timestamp as u64,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{vet, Submission, Verdict};
+    use tinyjson::JsonValue;
+
+    fn submission(answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            answer: answer.into(),
+            timestamp: 1_733_000_000,
+            verdict,
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait"),
+            Verdict::Throttled
+        );
+        assert_eq!(Verdict::from_response(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let log = [submission("42", Verdict::Wrong)];
+        assert_eq!(vet(&log, "42").is_err(), true);
+        assert_eq!(vet(&log, "43").is_ok(), true);
+    }
+
+    #[test]
+    fn refuses_out_of_bounds_answers() {
+        let log = [
+            submission("100", Verdict::TooHigh),
+            submission("150", Verdict::TooHigh),
+            submission("10", Verdict::TooLow),
+        ];
+        assert_eq!(vet(&log, "100").is_err(), true);
+        assert_eq!(vet(&log, "120").is_err(), true);
+        assert_eq!(vet(&log, "10").is_err(), true);
+        assert_eq!(vet(&log, "-5").is_err(), true);
+        assert_eq!(vet(&log, "99").is_ok(), true);
+    }
+
+    #[test]
+    fn refuses_unsubmittable_answers() {
+        assert_eq!(vet(&[], "").is_err(), true);
+        assert_eq!(vet(&[], "#.\n.#").is_err(), true);
+        assert_eq!(
+            vet(&[submission("1", Verdict::Correct)], "2").is_err(),
+            true
+        );
+        assert_eq!(
+            vet(&[submission("1", Verdict::Throttled)], "1").is_ok(),
+            true
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let entry = submission("abc", Verdict::TooLow);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert_eq!(Submission::try_from(line.as_str()).unwrap(), entry);
+    }
+}
@@ -1,23 +1,120 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    calendar::Calendar, metrics::Metrics, report::Status, stats::Stats, year, Day,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// Name of the timings file in the data folder of a year, i.e. `data/{year}/timings.json`.
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the schema of the timings file.
+/// Version 1 files (without a `version` key) stored the durations of the parts as display strings and are migrated
+/// when read. Version 2 stores every stage as an object with its duration in nanoseconds, statistics, status and
+/// resource metrics.
+pub const SCHEMA_VERSION: u32 = 2;
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+/// `*_nanos` hold the headline (median) duration of a stage, the full distribution is kept alongside.
+/// `parse_*` is only present for solutions that declare a parse stage.
+/// The `*_status` fields record how a stage ended, e.g. to tell a panicked part from an unsolved one.
+/// The `*_metrics` fields are only present for solutions timed with the `metrics` feature.
+/// `variant` names the variant of the solution, if it is not the plain solution of the day.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    pub variant: Option<String>,
+    pub parse_nanos: Option<f64>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    pub parse_status: Option<Status>,
+    pub part_1_status: Option<Status>,
+    pub part_2_status: Option<Status>,
+    pub parse_metrics: Option<Metrics>,
+    pub part_1_metrics: Option<Metrics>,
+    pub part_2_metrics: Option<Metrics>,
+}
+
+impl Timing {
+    /// A timing of `day` without any stage.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            variant: None,
+            parse_nanos: None,
+            part_1_nanos: None,
+            part_2_nanos: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_status: None,
+            part_1_status: None,
+            part_2_status: None,
+            parse_metrics: None,
+            part_1_metrics: None,
+            part_2_metrics: None,
+        }
+    }
+
+    /// Combined duration of all stages in nanoseconds.
+    pub fn total_nanos(&self) -> f64 {
+        [self.parse_nanos, self.part_1_nanos, self.part_2_nanos]
+            .iter()
+            .flatten()
+            .sum()
+    }
+
+    /// Combined resource metrics of all stages, if any were recorded.
+    pub fn total_metrics(&self) -> Option<Metrics> {
+        [self.parse_metrics, self.part_1_metrics, self.part_2_metrics]
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(|a, b| a.combine(&b))
+    }
+
+    /// Merges a newer timing of the same solution stage by stage. Stages that did not run in `new` are kept.
+    pub fn merge(&self, new: &Self) -> Self {
+        let mut merged = self.clone();
+
+        if new.parse_nanos.is_some() || new.parse_status.is_some() {
+            merged.parse_nanos = new.parse_nanos;
+            merged.parse_stats = new.parse_stats;
+            merged.parse_status = new.parse_status;
+            merged.parse_metrics = new.parse_metrics;
+        }
+
+        if new.part_1_nanos.is_some() || new.part_1_status.is_some() {
+            merged.part_1_nanos = new.part_1_nanos;
+            merged.part_1_stats = new.part_1_stats;
+            merged.part_1_status = new.part_1_status;
+            merged.part_1_metrics = new.part_1_metrics;
+        }
+
+        if new.part_2_nanos.is_some() || new.part_2_status.is_some() {
+            merged.part_2_nanos = new.part_2_nanos;
+            merged.part_2_stats = new.part_2_stats;
+            merged.part_2_status = new.part_2_status;
+            merged.part_2_metrics = new.part_2_metrics;
+        }
+
+        merged
+    }
 }
 
-/// Represents benchmark times for a set of days.
+/// Represents benchmark times for a set of days of one year.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    pub year: Option<u16>,
     pub data: Vec<Timing>,
 }
 
@@ -25,45 +122,114 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(year::data_dir().join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings of the current year from a JSON file. If not present, returns empty timings.
+    /// Files of an older schema are migrated in memory only, the next `cargo time --store` stores the current schema.
+    pub fn read_from_file() -> Result<Self, String> {
+        let path = year::data_dir().join(TIMINGS_FILE_NAME);
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Ok(Timings {
+                    year: year::try_current(),
+                    data: vec![],
+                })
+            }
+            Err(e) => return Err(format!("{}: {e}", path.display())),
+        };
+
+        let (timings, version) =
+            Timings::parse(&contents).map_err(|e| format!("{}: {e}", path.display()))?;
+
+        // NOTE: the file lives in the data folder of its year, even if it predates the `year` key.
+        let timings = Timings {
+            year: timings.year.or_else(year::try_current),
+            ..timings
+        };
+
+        if version < SCHEMA_VERSION {
+            eprintln!(
+                "Read {} in version {version}, run `cargo time --store` to store it as version {SCHEMA_VERSION}.",
+                path.display()
+            );
+        }
+
+        Ok(timings)
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Parses timings of any schema version. Returns them with the version they were stored in.
+    pub fn parse(contents: &str) -> Result<(Self, u32), String> {
+        let json = JsonValue::from_str(contents).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = schema_version(json)?;
+
+        let json_data = json
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        // NOTE: optional, timings stored before years were tracked do not have it.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u16);
+
+        let timings = Timings {
+            year,
+            data: json_data
+                .iter()
+                .map(|timing| timing_from_json(timing, version))
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok((timings, version))
+    }
+
+    /// Merge two sets of timings. Timings of the same solution are merged stage by stage, preferring `new`.
     pub fn merge(&self, new: &Self) -> Self {
-        let mut data: Vec<Timing> = vec![];
+        let mut data: Vec<Timing> = self.data.clone();
 
         for timing in &new.data {
-            data.push(timing.clone());
-        }
-
-        for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
-                data.push(timing.clone());
+            match data
+                .iter_mut()
+                .find(|t| t.day == timing.day && t.variant == timing.variant)
+            {
+                Some(existing) => *existing = existing.merge(timing),
+                None => data.push(timing.clone()),
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by(|a, b| (a.day, &a.variant).cmp(&(b.day, &b.variant)));
+        Timings {
+            year: new.year.or(self.year),
+            data,
+        }
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Returns `true` if every part of `day` is benched. The last day of an event only has a first part.
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        let has_part_two = self
+            .year
+            .is_none_or(|year| Calendar::of(year).has_part_two(day));
+
+        self.data.iter().any(|t| {
+            t.day == day && t.part_1_nanos.is_some() && (t.part_2_nanos.is_some() || !has_part_two)
+        })
     }
 }
 
@@ -73,6 +239,16 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
+        map.insert(
+            "year".into(),
+            value
+                .year
+                .map_or(JsonValue::Null, |x| JsonValue::Number(f64::from(x))),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -86,57 +262,157 @@ impl TryFrom<String> for Timings {
     type Error = String;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
-
-        let json_data = json
-            .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
-            .get("data")
-            .ok_or("expected JSON document to have key `data`.")?
-            .get::<Vec<JsonValue>>()
-            .ok_or("expected `json.data` to be an array.")?;
-
-        Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
-        })
+        Timings::parse(&value).map(|(timings, _)| timings)
     }
 }
 
-/* -------------------------------------------------------------------------- */
+/// Serializes a stage as an object, or `null` if it did not run.
+fn stage_to_json(
+    nanos: Option<f64>,
+    stats: Option<&Stats>,
+    status: Option<Status>,
+    metrics: Option<&Metrics>,
+) -> JsonValue {
+    if nanos.is_none() && stats.is_none() && status.is_none() && metrics.is_none() {
+        return JsonValue::Null;
+    }
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "nanos".into(),
+        nanos.map_or(JsonValue::Null, JsonValue::Number),
+    );
+    map.insert(
+        "stats".into(),
+        stats.map_or(JsonValue::Null, JsonValue::from),
+    );
+    map.insert(
+        "status".into(),
+        status.map_or(JsonValue::Null, |x| JsonValue::String(x.to_string())),
+    );
+    map.insert(
+        "metrics".into(),
+        metrics.map_or(JsonValue::Null, JsonValue::from),
+    );
+
+    JsonValue::Object(map)
+}
 
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        if let Some(variant) = &value.variant {
+            map.insert("variant".into(), JsonValue::String(variant.clone()));
+        }
 
+        map.insert(
+            "parse".into(),
+            stage_to_json(
+                value.parse_nanos,
+                value.parse_stats.as_ref(),
+                value.parse_status,
+                value.parse_metrics.as_ref(),
+            ),
+        );
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            stage_to_json(
+                value.part_1_nanos,
+                value.part_1_stats.as_ref(),
+                value.part_1_status,
+                value.part_1_metrics.as_ref(),
+            ),
         );
-
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            stage_to_json(
+                value.part_2_nanos,
+                value.part_2_stats.as_ref(),
+                value.part_2_status,
+                value.part_2_metrics.as_ref(),
+            ),
         );
 
         JsonValue::Object(map)
     }
 }
 
+/// Reads an optional value of a JSON object, `null` and a missing key are `None`.
+fn optional<'a, T>(
+    json: &'a HashMap<String, JsonValue>,
+    key: &str,
+    parse: impl Fn(&'a JsonValue) -> Result<T, String>,
+) -> Result<Option<T>, String> {
+    json.get(key)
+        .filter(|v| !v.is_null())
+        .map(parse)
+        .transpose()
+}
+
+/// Reads the optional status of a stage, stored as `timing.<key>`.
+fn status_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<Status>, String> {
+    optional(json, key, |v| {
+        v.get::<String>()
+            .ok_or(format!("Expected timing.{key} to be null or string."))?
+            .parse()
+    })
+}
+
+/// A stage as read from JSON: duration, statistics, status and metrics.
+type Stage = (Option<f64>, Option<Stats>, Option<Status>, Option<Metrics>);
+
+/// Reads a stage object, stored as `timing.<key>`.
+fn stage_from_json(json: &HashMap<String, JsonValue>, key: &str) -> Result<Stage, String> {
+    let Some(stage) = optional(json, key, |v| {
+        v.get::<HashMap<String, JsonValue>>()
+            .ok_or(format!("Expected timing.{key} to be null or an object."))
+    })?
+    else {
+        return Ok((None, None, None, None));
+    };
+
+    let nanos = optional(stage, "nanos", |v| {
+        v.get::<f64>().copied().ok_or(format!(
+            "Expected timing.{key}.nanos to be null or a number."
+        ))
+    })?;
+
+    Ok((
+        nanos,
+        optional(stage, "stats", Stats::try_from)?,
+        optional(stage, "status", |v| {
+            v.get::<String>()
+                .ok_or(format!(
+                    "Expected timing.{key}.status to be null or string."
+                ))?
+                .parse()
+        })?,
+        optional(stage, "metrics", Metrics::try_from)?,
+    ))
+}
+
+/// Reads the day and variant of a timing, which are the same in all versions.
+fn day_and_variant(json: &HashMap<String, JsonValue>) -> Result<(Day, Option<String>), String> {
+    let day = json
+        .get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.")?;
+
+    let variant = optional(json, "variant", |v| {
+        v.get::<String>()
+            .cloned()
+            .ok_or("Expected timing.variant to be null or string.".into())
+    })?;
+
+    Ok((day, variant))
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -145,89 +421,257 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+        let (day, variant) = day_and_variant(json)?;
+        let (parse_nanos, parse_stats, parse_status, parse_metrics) =
+            stage_from_json(json, "parse")?;
+        let (part_1_nanos, part_1_stats, part_1_status, part_1_metrics) =
+            stage_from_json(json, "part_1")?;
+        let (part_2_nanos, part_2_stats, part_2_status, part_2_metrics) =
+            stage_from_json(json, "part_2")?;
+
+        Ok(Timing {
+            day,
+            variant,
+            parse_nanos,
+            part_1_nanos,
+            part_2_nanos,
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            parse_status,
+            part_1_status,
+            part_2_status,
+            parse_metrics,
+            part_1_metrics,
+            part_2_metrics,
+        })
+    }
+}
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+/* -------------------------------------------------------------------------- */
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+/// Reads the schema version of a JSON document that holds timings. Documents without a version are version 1.
+pub(crate) fn schema_version(json: &HashMap<String, JsonValue>) -> Result<u32, String> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let version = json
+        .get("version")
+        .map(|v| {
+            v.get::<f64>()
+                .map(|x| *x as u32)
+                .ok_or("Expected version to be a number.")
+        })
+        .transpose()?
+        .unwrap_or(1);
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+    if version == 0 || version > SCHEMA_VERSION {
+        return Err(format!(
+            "version {version} is not supported, the latest version is {SCHEMA_VERSION}."
+        ));
+    }
 
-        Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+    Ok(version)
+}
+
+/// Reads a timing stored in the given schema version.
+pub(crate) fn timing_from_json(value: &JsonValue, version: u32) -> Result<Timing, String> {
+    match version {
+        1 => timing_from_v1(value),
+        _ => Timing::try_from(value),
+    }
+}
+
+/// Parses a duration as formatted by `Debug`, e.g. `74.13µs` or `1.2s`, into nanoseconds.
+fn parse_display_nanos(value: &str) -> Option<f64> {
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    Some(number * factor)
+}
+
+/// Reads a timing of schema version 1, which stored the durations of stages as display strings next to flat
+/// `*_stats`, `*_status` and `*_metrics` keys. Durations are taken from the statistics if present.
+fn timing_from_v1(value: &JsonValue) -> Result<Timing, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?;
+
+    let (day, variant) = day_and_variant(json)?;
+
+    let display = |key: &str| {
+        optional(json, key, |v| {
+            let display = v
+                .get::<String>()
+                .ok_or(format!("Expected timing.{key} to be null or string."))?;
+            parse_display_nanos(display).ok_or(format!(
+                "Expected timing.{key} to be a duration, got `{display}`."
+            ))
         })
+    };
+
+    // NOTE: `part_1`, `part_2` and `total_nanos` were always written by version 1, the other keys are optional.
+    for key in ["part_1", "part_2"] {
+        json.get(key)
+            .ok_or(format!("Expected timing.{key} to be null or string."))?;
     }
+    json.get("total_nanos")
+        .and_then(|v| v.get::<f64>())
+        .ok_or("Expected timing.total_nanos to be a number.")?;
+
+    let stats = |key: &str| optional(json, key, Stats::try_from);
+    let metrics = |key: &str| optional(json, key, Metrics::try_from);
+
+    let parse_stats = stats("parse_stats")?;
+    let part_1_stats = stats("part_1_stats")?;
+    let part_2_stats = stats("part_2_stats")?;
+
+    Ok(Timing {
+        day,
+        variant,
+        parse_nanos: parse_stats.map(|s| s.median_nanos).or(display("parse")?),
+        part_1_nanos: part_1_stats.map(|s| s.median_nanos).or(display("part_1")?),
+        part_2_nanos: part_2_stats.map(|s| s.median_nanos).or(display("part_2")?),
+        parse_stats,
+        part_1_stats,
+        part_2_stats,
+        parse_status: status_from_json(json, "parse_status")?,
+        part_1_status: status_from_json(json, "part_1_status")?,
+        part_2_status: status_from_json(json, "part_2_status")?,
+        parse_metrics: metrics("parse_metrics")?,
+        part_1_metrics: metrics("part_1_metrics")?,
+        part_2_metrics: metrics("part_2_metrics")?,
+    })
 }
 
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
-mod tests {
-    use crate::day;
+pub(crate) mod tests {
+    use crate::{
+        day,
+        template::{stats::Stats, Day},
+    };
 
     use super::{Timing, Timings};
 
+    /// Stats of `samples` runs around `median_nanos`, shared by the tests of the modules that read timings.
+    pub(crate) fn mock_stats(median_nanos: f64, stddev_nanos: f64, samples: u64) -> Stats {
+        Stats {
+            samples,
+            outliers: 0,
+            min_nanos: median_nanos,
+            median_nanos,
+            mean_nanos: median_nanos,
+            p95_nanos: median_nanos,
+            stddev_nanos,
+        }
+    }
+
+    /// A benchmarked day, each part takes the median of its stats.
+    pub(crate) fn mock_timing(day: Day, part_1: Option<Stats>, part_2: Option<Stats>) -> Timing {
+        Timing {
+            part_1_nanos: part_1.map(|s| s.median_nanos),
+            part_2_nanos: part_2.map(|s| s.median_nanos),
+            part_1_stats: part_1,
+            part_2_stats: part_2,
+            ..Timing::new(day)
+        }
+    }
+
     fn get_mock_timings() -> Timings {
         Timings {
+            year: None,
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1_nanos: Some(1e10),
+                    part_2_nanos: Some(2e10),
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1_nanos: Some(3e10),
+                    part_2_nanos: Some(4e10),
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
+                    part_1_nanos: Some(4e10),
+                    ..Timing::new(day!(4))
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{
+                report::Status,
+                timings::{Timings, SCHEMA_VERSION},
+            },
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
-            let timings = Timings::try_from(json).unwrap();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "stats": null, "status": "solved", "metrics": null }, "part_2": null }] }"#;
+            let (timings, version) = Timings::parse(json).unwrap();
+            assert_eq!(version, 2);
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
-            assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_nanos, Some(1_000_000_f64));
+            assert_eq!(timing.part_1_status, Some(Status::Solved));
+            assert_eq!(timing.part_2_nanos, None);
+            assert_eq!(timing.total_nanos(), 1_000_000_f64);
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "stats": { "samples": 10, "outliers": 1, "min_nanos": 900000, "median_nanos": 1000000, "mean_nanos": 1010000, "p95_nanos": 1200000, "stddev_nanos": 5000 } } }] }"#;
+            let (timings, _) = Timings::parse(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(stats.p95_nanos, 1_200_000_f64);
+            assert_eq!(timing.part_2_stats, None);
+            assert_eq!(timing.parse_nanos, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_variant() {
+            let json = r#"{ "version": 2, "data": [{ "day": "13", "variant": "z3", "part_1": { "nanos": 1000000 } }] }"#;
+            let (timings, _) = Timings::parse(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.variant.as_deref(), Some("z3"));
         }
 
         #[test]
         fn handles_empty_timings() {
-            let json = r#"{ "data": [] }"#.to_string();
-            let timings = Timings::try_from(json).unwrap();
+            let json = r#"{ "version": 2, "data": [] }"#;
+            let (timings, _) = Timings::parse(json).unwrap();
             assert_eq!(timings.data.len(), 0);
+            assert_eq!(timings.year, None);
+        }
+
+        #[test]
+        fn handles_year() {
+            let json = r#"{ "version": 2, "year": 2023, "data": [] }"#;
+            let (timings, _) = Timings::parse(json).unwrap();
+            assert_eq!(timings.year, Some(2023));
+        }
+
+        #[test]
+        fn rejects_unsupported_versions() {
+            let json = format!(r#"{{ "version": {}, "data": [] }}"#, SCHEMA_VERSION + 1);
+            let err = Timings::parse(&json).unwrap_err();
+            assert_eq!(err.contains("is not supported"), true);
         }
 
         #[test]
@@ -240,25 +684,84 @@ mod tests {
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let json =
+                r#"{ "version": 2, "data": [{ "day": "01", "part_1": "1ms" }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
     }
 
+    mod migration {
+        use crate::{
+            day,
+            template::{report::Status, timings::Timings},
+        };
+
+        #[test]
+        fn migrates_display_strings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2.5µs", "part_1": "1ms", "part_2": "1.5s", "total_nanos": 1000000000 }] }"#;
+            let (timings, version) = Timings::parse(json).unwrap();
+            assert_eq!(version, 1);
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.parse_nanos, Some(2_500_f64));
+            assert_eq!(timing.part_1_nanos, Some(1_000_000_f64));
+            assert_eq!(timing.part_2_nanos, Some(1_500_000_000_f64));
+        }
+
+        #[test]
+        fn prefers_median_of_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "outliers": 1, "min_nanos": 900000, "median_nanos": 1012345, "mean_nanos": 1010000, "p95_nanos": 1200000, "stddev_nanos": 5000 }, "part_2_stats": null }] }"#;
+            let (timings, _) = Timings::parse(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_nanos, Some(1_012_345_f64));
+            assert_eq!(timing.part_1_stats.unwrap().samples, 10);
+            assert_eq!(timing.part_2_nanos, None);
+        }
+
+        #[test]
+        fn migrates_status_and_variant() {
+            let json = r#"{ "year": 2023, "data": [{ "day": "13", "variant": "z3", "part_1": null, "part_2": "1ms", "total_nanos": 1000000, "part_1_status": "panicked", "part_2_status": "solved" }] }"#;
+            let (timings, _) = Timings::parse(json).unwrap();
+            assert_eq!(timings.year, Some(2023));
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.variant.as_deref(), Some("z3"));
+            assert_eq!(timing.part_1_status, Some(Status::Panicked));
+            assert_eq!(timing.part_2_status, Some(Status::Solved));
+            assert_eq!(timing.parse_status, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_timings() {
+            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#;
+            Timings::parse(json).unwrap();
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_units() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1 fortnight", "part_2": null, "total_nanos": 0 }] }"#;
+            Timings::parse(json).unwrap();
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{Timings, SCHEMA_VERSION};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let value = JsonValue::from(timings);
+            let json = value.get::<HashMap<String, JsonValue>>().unwrap();
             assert_eq!(
-                value
-                    .get::<HashMap<String, JsonValue>>()
-                    .unwrap()
-                    .get("data")
+                json.get("version").unwrap().get::<f64>(),
+                Some(&f64::from(SCHEMA_VERSION))
+            );
+            assert_eq!(
+                json.get("data")
                     .unwrap()
                     .get::<Vec<JsonValue>>()
                     .unwrap()
@@ -266,6 +769,15 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let (parsed, version) = Timings::parse(&json).unwrap();
+            assert_eq!(version, SCHEMA_VERSION);
+            assert_eq!(parsed.data, timings.data);
+        }
     }
 
     mod is_day_complete {
@@ -277,50 +789,69 @@ mod tests {
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
+                year: None,
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    part_1_nanos: Some(1e6),
+                    part_2_nanos: Some(2e6),
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
+                year: None,
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    part_1_nanos: Some(1e6),
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+
+        #[test]
+        fn handles_last_day_without_part_two() {
+            let mut timing = Timing {
+                part_1_nanos: Some(1e6),
+                ..Timing::new(day!(12))
+            };
+
+            let timings = Timings {
+                year: Some(2025),
+                data: vec![timing.clone()],
+            };
+            assert_eq!(timings.is_day_complete(day!(12)), true);
+
+            timing.day = day!(11);
+            let timings = Timings {
+                year: Some(2025),
+                data: vec![timing],
+            };
+            assert_eq!(timings.is_day_complete(day!(11)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                year: None,
+                data: vec![Timing::new(day!(1))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                report::Status,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -329,12 +860,8 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                year: None,
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,11 +876,10 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
+                year: None,
                 data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    part_2_nanos: Some(5e10),
+                    ..Timing::new(day!(2))
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,10 +887,49 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].part_1_nanos, Some(3e10));
+            assert_eq!(merged.data[1].part_2_nanos, Some(5e10));
+            assert_eq!(merged.data[1].total_nanos(), 8e10);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn replaces_stages_that_ran() {
+            let timings = get_mock_timings();
+
+            // a panicked part has no duration, its stale duration must not survive the merge.
+            let other = Timings {
+                year: None,
+                data: vec![Timing {
+                    part_1_status: Some(Status::Panicked),
+                    ..Timing::new(day!(1))
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data[0].part_1_nanos, None);
+            assert_eq!(merged.data[0].part_1_status, Some(Status::Panicked));
+            assert_eq!(merged.data[0].part_2_nanos, Some(2e10));
+        }
+
+        #[test]
+        fn keeps_variants_apart() {
+            let timings = get_mock_timings();
+
+            let mut other = timings.clone();
+            other.data.truncate(1);
+            other.data[0].variant = Some("fast".into());
+            other.data[0].part_1_nanos = Some(1.0);
+
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].variant, None);
+            assert_eq!(merged.data[0].total_nanos(), 3e+10);
+            assert_eq!(merged.data[1].day, day!(1));
+            assert_eq!(merged.data[1].variant.as_deref(), Some("fast"));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
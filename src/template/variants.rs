@@ -0,0 +1,203 @@
+/// A day can be solved by several variants of a solution, e.g. `src/bin/13_linalg.rs` and `src/bin/13_z3.rs`.
+/// Variants are discovered from the solution binaries, the plain `src/bin/{day}.rs` is the variant without a name.
+/// The README shows the primary variant of a day, configured per year in `data/{year}/variants.json`
+/// (e.g. `{ "13": "linalg" }`). Without configuration, the plain solution or else the first variant is primary.
+use std::{collections::HashMap, fs, io::ErrorKind, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{year, Day};
+
+const VARIANTS_FILE_NAME: &str = "variants.json";
+
+/// A solution binary of a day.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Solution {
+    pub day: Day,
+    pub variant: Option<String>,
+}
+
+impl Solution {
+    /// Name of the solution binary in the current year, e.g. `13_z3`.
+    pub fn bin_name(&self) -> String {
+        let base = year::bin_name(year::current(), self.day);
+        match &self.variant {
+            Some(variant) => format!("{base}_{variant}"),
+            None => base,
+        }
+    }
+
+    /// Label of the solution in output, e.g. `Day 13 (z3)`.
+    pub fn label(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("Day {} ({variant})", self.day),
+            None => format!("Day {}", self.day),
+        }
+    }
+}
+
+/// Extracts the variants of a day from the names of the solution binaries, given the binary name of the plain solution.
+/// The plain solution comes first, the other variants are sorted by name.
+fn variants_of_bins<'a>(base: &str, bins: impl Iterator<Item = &'a str>) -> Vec<Option<String>> {
+    let mut variants: Vec<Option<String>> = bins
+        .filter_map(|bin| {
+            if bin == base {
+                return Some(None);
+            }
+
+            bin.strip_prefix(base)
+                .and_then(|rest| rest.strip_prefix('_'))
+                .filter(|variant| !variant.is_empty())
+                .map(|variant| Some(variant.to_string()))
+        })
+        .collect();
+
+    variants.sort();
+    variants
+}
+
+/// Discovers the solutions of a day in the current year, an empty list means the day was not solved yet.
+pub fn discover(day: Day) -> Vec<Solution> {
+    let Ok(entries) = fs::read_dir("src/bin") else {
+        return vec![];
+    };
+
+    let bins: Vec<String> = entries
+        .map_while(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            (path.extension()? == "rs").then_some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+
+    variants_of_bins(
+        &year::bin_name(year::current(), day),
+        bins.iter().map(String::as_str),
+    )
+    .into_iter()
+    .map(|variant| Solution { day, variant })
+    .collect()
+}
+
+/// Finds the solution of a day to run. Without `variant`, the primary solution is picked.
+pub fn find(day: Day, variant: Option<&str>) -> Result<Solution, String> {
+    let solutions = discover(day);
+
+    let Some(variant) = variant else {
+        let configured = read_primary();
+        return primary(&solutions, configured.get(&day).map(String::as_str))
+            .cloned()
+            .ok_or_else(|| format!("day {day} has no solution."));
+    };
+
+    solutions
+        .iter()
+        .find(|s| s.variant.as_deref() == Some(variant))
+        .cloned()
+        .ok_or_else(|| {
+            let names: Vec<_> = solutions
+                .iter()
+                .filter_map(|s| s.variant.as_deref())
+                .collect();
+            format!(
+                "day {day} has no variant `{variant}`, available: {}.",
+                if names.is_empty() {
+                    "none".into()
+                } else {
+                    names.join(", ")
+                }
+            )
+        })
+}
+
+/// Picks the primary of the solutions of a day, given the configured primary variant.
+pub fn primary<'a>(solutions: &'a [Solution], configured: Option<&str>) -> Option<&'a Solution> {
+    configured
+        .and_then(|c| solutions.iter().find(|s| s.variant.as_deref() == Some(c)))
+        .or_else(|| solutions.iter().find(|s| s.variant.is_none()))
+        .or_else(|| solutions.first())
+}
+
+/// Reads the configured primary variants of the current year. A missing file configures none.
+pub fn read_primary() -> HashMap<Day, String> {
+    let path = year::data_dir().join(VARIANTS_FILE_NAME);
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return HashMap::new(),
+        Err(e) => {
+            eprintln!("Failed to read {}: {e}", path.display());
+            return HashMap::new();
+        }
+    };
+
+    parse_primary(&contents).unwrap_or_else(|e| {
+        eprintln!("Failed to read {}: {e}", path.display());
+        HashMap::new()
+    })
+}
+
+fn parse_primary(contents: &str) -> Result<HashMap<Day, String>, String> {
+    let json = JsonValue::from_str(contents).or(Err("not valid JSON."))?;
+
+    json.get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected variants to be a JSON object.")?
+        .iter()
+        .map(|(day, variant)| {
+            let day = Day::from_str(day).map_err(|_| format!("Expected `{day}` to be a day."))?;
+            let variant = variant
+                .get::<String>()
+                .ok_or_else(|| format!("Expected variants.{day} to be a string."))?;
+            Ok((day, variant.clone()))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_primary, primary, variants_of_bins, Solution};
+    use crate::day;
+
+    #[test]
+    fn discovers_variants() {
+        let bins = ["12", "13_z3", "13_linalg", "13", "14", "2023_13_old"];
+        assert_eq!(
+            variants_of_bins("13", bins.into_iter()),
+            vec![None, Some("linalg".into()), Some("z3".into())]
+        );
+        assert_eq!(variants_of_bins("15", bins.into_iter()), vec![]);
+        assert_eq!(
+            variants_of_bins("2023_13", bins.into_iter()),
+            vec![Some("old".into())]
+        );
+    }
+
+    #[test]
+    fn picks_primary_variant() {
+        let solution = |variant: Option<&str>| Solution {
+            day: day!(13),
+            variant: variant.map(String::from),
+        };
+        let variants = [solution(Some("linalg")), solution(Some("z3"))];
+
+        assert_eq!(primary(&variants, Some("z3")), Some(&variants[1]));
+        assert_eq!(primary(&variants, Some("nope")), Some(&variants[0]));
+        assert_eq!(primary(&variants, None), Some(&variants[0]));
+
+        let with_plain = [solution(None), solution(Some("z3"))];
+        assert_eq!(primary(&with_plain, None), Some(&with_plain[0]));
+        assert_eq!(primary(&[], None), None);
+    }
+
+    #[test]
+    fn parses_primary_variants() {
+        let configured = parse_primary(r#"{ "13": "linalg" }"#).unwrap();
+        assert_eq!(
+            configured.get(&day!(13)).map(String::as_str),
+            Some("linalg")
+        );
+        assert_eq!(parse_primary(r#"{ "13": 1 }"#).is_err(), true);
+    }
+}
@@ -0,0 +1,389 @@
+/// Building blocks of `cargo watch-day`: which files belong to a day, how to notice that they changed, how to read the
+/// outcome of `cargo test`, and the panel that summarizes a run.
+/// Changes are detected by polling modification times, which needs no platform support and catches editors that
+/// replace files instead of writing them in place.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::template::{
+    report::{Report, Status},
+    year, Day, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Number of lines of a failed build, test or solution that the panel shows.
+pub const FAILURE_LINES: usize = 6;
+
+/// Modification times of the watched files.
+pub type Snapshot = HashMap<PathBuf, SystemTime>;
+
+/// Files that affect a day: its solutions `src/bin/{day}*.rs`, the shared `src/lib.rs`, and its examples and input
+/// `data/{year}/{examples,inputs}/{day}*.txt`.
+pub fn watched_files(day: Day) -> Vec<PathBuf> {
+    let bin = year::bin_name(year::current(), day);
+    let data = year::data_dir();
+    let day = day.to_string();
+
+    let mut files = vec![PathBuf::from("src/lib.rs")];
+    files.extend(files_with_prefix(Path::new("src/bin"), &bin, "rs"));
+    files.extend(files_with_prefix(&data.join("examples"), &day, "txt"));
+    files.extend(files_with_prefix(&data.join("inputs"), &day, "txt"));
+    files
+}
+
+fn files_with_prefix(dir: &Path, prefix: &str, extension: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .map_while(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|x| x == extension)
+                && path
+                    .file_stem()
+                    .and_then(|x| x.to_str())
+                    .is_some_and(|x| x.starts_with(prefix))
+        })
+        .collect();
+
+    files.sort();
+    files
+}
+
+/// Takes the modification times of `files`. Files that cannot be read are left out, so that they count as removed.
+pub fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .filter_map(|path| Some((path.clone(), fs::metadata(path).ok()?.modified().ok()?)))
+        .collect()
+}
+
+/// Returns a file that was changed, added or removed between two snapshots.
+pub fn changed(before: &Snapshot, after: &Snapshot) -> Option<PathBuf> {
+    let mut paths: Vec<&PathBuf> = before.keys().chain(after.keys()).collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .find(|path| before.get(*path) != after.get(*path))
+        .cloned()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Outcome of the example tests.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tests {
+    /// The tests did not compile, with the first lines of the errors.
+    BuildFailed(Vec<String>),
+    /// The tests ran. `failures` holds the names of failed tests and the first lines of their output.
+    Ran {
+        passed: usize,
+        failed: usize,
+        failures: Vec<String>,
+    },
+}
+
+impl Tests {
+    /// Returns `true` if the tests compiled and none of them failed.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Tests::Ran { failed: 0, .. })
+    }
+}
+
+/// Reads the outcome of `cargo test` from its stdout and stderr.
+/// The test harness prints a `test result:` line for every test binary, without any the build failed.
+pub fn parse_test_output(stdout: &str, stderr: &str) -> Tests {
+    let mut totals: Option<(usize, usize)> = None;
+
+    for line in stdout
+        .lines()
+        .filter_map(|l| l.strip_prefix("test result: "))
+    {
+        let count = |label: &str| {
+            line.split([';', '.'])
+                .filter_map(|x| x.trim().strip_suffix(label))
+                .find_map(|x| x.trim().parse::<usize>().ok())
+                .unwrap_or(0)
+        };
+
+        let (passed, failed) = totals.unwrap_or_default();
+        totals = Some((passed + count("passed"), failed + count("failed")));
+    }
+
+    let Some((passed, failed)) = totals else {
+        let errors: Vec<String> = stderr
+            .lines()
+            .skip_while(|l| !l.starts_with("error"))
+            .take(FAILURE_LINES)
+            .map(String::from)
+            .collect();
+
+        return Tests::BuildFailed(if errors.is_empty() {
+            stderr
+                .lines()
+                .take(FAILURE_LINES)
+                .map(String::from)
+                .collect()
+        } else {
+            errors
+        });
+    };
+
+    let mut failures = vec![];
+    let mut lines = stdout.lines();
+
+    // NOTE: the output of every failed test is printed in a section `---- <name> stdout ----`.
+    while let Some(line) = lines.next() {
+        let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|x| x.strip_suffix(" stdout ----"))
+        else {
+            continue;
+        };
+
+        failures.push(name.to_string());
+        failures.extend(
+            lines
+                .by_ref()
+                .take_while(|l| !l.is_empty())
+                .filter(|l| !l.starts_with("note:"))
+                .take(FAILURE_LINES)
+                .map(|l| format!("  {l}")),
+        );
+    }
+
+    Tests::Ran {
+        passed,
+        failed,
+        failures,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Outcome of running the solution against the real input.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Solve {
+    /// The solution was not run because the tests failed.
+    Skipped,
+    /// The solution ran and emitted records.
+    Reports(Vec<Report>),
+    /// The solution did not emit any record, with the last lines of its output.
+    Failed(Vec<String>),
+}
+
+fn stage_label(stage: &str) -> String {
+    match stage.strip_prefix("part_") {
+        Some(part) => format!("Part {part}"),
+        None => "Parse".into(),
+    }
+}
+
+fn format_report(report: &Report) -> String {
+    let label = stage_label(&report.stage);
+    let duration = format!("({:.1?})", report.stats.median());
+
+    match (report.status, &report.answer) {
+        (Status::Solved, Some(answer)) => {
+            format!("{label:<7} ✔ {ANSI_BOLD}{answer}{ANSI_RESET}  {duration}")
+        }
+        (Status::Solved, None) => format!("{label:<7} ✔ {duration}"),
+        (Status::Unsolved, _) => format!("{label:<7} – unsolved  {duration}"),
+        (status, _) => match &report.message {
+            Some(message) => format!("{label:<7} ✘ {status}: {message}"),
+            None => format!("{label:<7} ✘ {status}"),
+        },
+    }
+}
+
+/// Renders the panel of a run. `tests` is `None` while the tests are running, `solve` while the solution is.
+pub fn render_panel(
+    title: &str,
+    trigger: Option<&Path>,
+    tests: Option<&Tests>,
+    solve: Option<&Solve>,
+) -> Vec<String> {
+    let mut lines = vec![format!("{ANSI_BOLD}{title}{ANSI_RESET}")];
+
+    lines.push(match trigger {
+        Some(path) => format!("Changed: {}", path.display()),
+        None => "Initial run".into(),
+    });
+    lines.push(String::new());
+
+    match tests {
+        None => lines.push("Tests   … running".into()),
+        Some(Tests::BuildFailed(errors)) => {
+            lines.push("Tests   ✘ build failed".into());
+            lines.extend(errors.iter().map(|l| format!("  {l}")));
+        }
+        Some(Tests::Ran {
+            passed, failed: 0, ..
+        }) => lines.push(format!("Tests   ✔ {passed} passed")),
+        Some(Tests::Ran {
+            passed,
+            failed,
+            failures,
+        }) => {
+            lines.push(format!("Tests   ✘ {failed} failed, {passed} passed"));
+            lines.extend(failures.iter().map(|l| format!("  {l}")));
+        }
+    }
+
+    match (tests, solve) {
+        (None, _) => {}
+        (Some(_), None) => lines.push("Input   … running".into()),
+        (Some(_), Some(Solve::Skipped)) => lines.push("Input   – skipped, tests failed".into()),
+        (Some(_), Some(Solve::Failed(output))) => {
+            lines.push("Input   ✘ no result".into());
+            lines.extend(output.iter().map(|l| format!("  {l}")));
+        }
+        (Some(_), Some(Solve::Reports(reports))) => {
+            lines.extend(reports.iter().map(format_report));
+        }
+    }
+
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use super::{changed, parse_test_output, render_panel, Snapshot, Solve, Tests};
+    use crate::{
+        day,
+        template::{report::Report, stats::Stats},
+    };
+
+    #[test]
+    fn detects_changed_files() {
+        let now = SystemTime::UNIX_EPOCH;
+        let before = Snapshot::from([
+            (PathBuf::from("src/bin/01.rs"), now),
+            (PathBuf::from("src/lib.rs"), now),
+        ]);
+
+        assert_eq!(changed(&before, &before.clone()), None);
+
+        let mut after = before.clone();
+        after.insert("src/lib.rs".into(), now + Duration::from_secs(1));
+        assert_eq!(changed(&before, &after), Some("src/lib.rs".into()));
+
+        let mut after = before.clone();
+        after.insert("data/2024/examples/01-2.txt".into(), now);
+        assert_eq!(
+            changed(&before, &after),
+            Some("data/2024/examples/01-2.txt".into())
+        );
+
+        let mut after = before.clone();
+        after.remove(&PathBuf::from("src/bin/01.rs"));
+        assert_eq!(changed(&before, &after), Some("src/bin/01.rs".into()));
+    }
+
+    #[test]
+    fn parses_passing_tests() {
+        let stdout = "\nrunning 2 tests\ntest tests::test_part_one ... ok\ntest tests::test_part_two ... ok\n\ntest result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n";
+        assert_eq!(
+            parse_test_output(stdout, ""),
+            Tests::Ran {
+                passed: 2,
+                failed: 0,
+                failures: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn parses_failing_tests() {
+        let stdout = [
+            "running 2 tests",
+            "test tests::test_part_one ... ok",
+            "test tests::test_part_two ... FAILED",
+            "",
+            "failures:",
+            "",
+            "---- tests::test_part_two stdout ----",
+            "thread 'tests::test_part_two' panicked at src/bin/01.rs:40:9:",
+            "assertion `left == right` failed",
+            "  left: Some(3)",
+            " right: Some(4)",
+            "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+            "",
+            "",
+            "failures:",
+            "    tests::test_part_two",
+            "",
+            "test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s",
+        ]
+        .join("\n");
+
+        let Tests::Ran {
+            passed,
+            failed,
+            failures,
+        } = parse_test_output(&stdout, "")
+        else {
+            panic!("expected tests to run");
+        };
+
+        assert_eq!((passed, failed), (1, 1));
+        assert_eq!(failures.len(), 5);
+        assert_eq!(failures[0], "tests::test_part_two");
+        assert_eq!(failures[4], "   right: Some(4)");
+    }
+
+    #[test]
+    fn parses_build_failures() {
+        let stderr = "   Compiling advent_of_code v0.11.0\nerror[E0425]: cannot find value `x` in this scope\n --> src/bin/01.rs:5:5\n";
+        let tests = parse_test_output("", stderr);
+        assert_eq!(tests.is_success(), false);
+        assert_eq!(
+            tests,
+            Tests::BuildFailed(vec![
+                "error[E0425]: cannot find value `x` in this scope".into(),
+                " --> src/bin/01.rs:5:5".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn renders_panel() {
+        let stats = Stats::from_samples(&[Duration::from_micros(1500)]);
+        let tests = Tests::Ran {
+            passed: 2,
+            failed: 0,
+            failures: vec![],
+        };
+        let solve = Solve::Reports(vec![
+            Report::part(day!(1), 1, Some(&42), stats),
+            Report::part::<u32>(day!(1), 2, None, stats),
+        ]);
+
+        let lines = render_panel(
+            "Day 01",
+            Some(&PathBuf::from("src/bin/01.rs")),
+            Some(&tests),
+            Some(&solve),
+        );
+        assert_eq!(lines[1], "Changed: src/bin/01.rs");
+        assert_eq!(lines[3], "Tests   ✔ 2 passed");
+        assert_eq!(lines[4], "Part 1  ✔ \x1b[1m42\x1b[0m  (1.5ms)");
+        assert_eq!(lines[5], "Part 2  – unsolved  (1.5ms)");
+
+        let lines = render_panel("Day 01", None, Some(&tests), None);
+        assert_eq!(lines.last().unwrap(), "Input   … running");
+    }
+}
@@ -0,0 +1,178 @@
+/// The event year a command or solution works on.
+/// The active year is configured as `AOC_YEAR` in `.cargo/config.toml`, its solutions live in `src/bin/{day}.rs`.
+/// Solutions of archived years live in `src/bin/{year}_{day}.rs`, all data of a year lives in `data/{year}`.
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::template::Day;
+
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+static SELECTED: OnceLock<u16> = OnceLock::new();
+
+/// Selects the year for the current process, i.e. the value of `--year`.
+pub fn select(year: u16) {
+    let _ = SELECTED.set(year);
+}
+
+/// The active year, as configured in `.cargo/config.toml`.
+pub fn active() -> Option<u16> {
+    env::var(YEAR_ENV).ok().and_then(|x| x.parse().ok())
+}
+
+/// The year to work on. In order of precedence:
+///  1. the year selected with `--year`.
+///  2. the year of an archived solution binary (or its tests), taken from its name.
+///  3. the active year.
+pub fn try_current() -> Option<u16> {
+    SELECTED
+        .get()
+        .copied()
+        .or_else(year_of_current_exe)
+        .or_else(active)
+}
+
+/// Like [`try_current`], panics if no year is configured.
+pub fn current() -> u16 {
+    try_current().expect("no year configured, set AOC_YEAR in .cargo/config.toml or pass --year")
+}
+
+fn year_of_current_exe() -> Option<u16> {
+    let exe = env::current_exe().ok()?;
+    year_of_bin(exe.file_name()?.to_str()?)
+}
+
+/// Extracts the year from the name of an archived solution binary, e.g. `2023_01` or `2023_01-9f8c…` for tests.
+pub fn year_of_bin(name: &str) -> Option<u16> {
+    let (year, rest) = name.split_once('_')?;
+    let day = rest.get(..2)?;
+
+    if year.len() != 4 || !day.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    year.parse().ok()
+}
+
+/// Name of the solution binary of `day` in `year`.
+pub fn bin_name(year: u16, day: Day) -> String {
+    if Some(year) == active() {
+        day.to_string()
+    } else {
+        format!("{year}_{day}")
+    }
+}
+
+/// Root of the data of the current year, i.e. `data/{year}`.
+pub fn data_dir() -> PathBuf {
+    data_dir_of(current())
+}
+
+pub fn data_dir_of(year: u16) -> PathBuf {
+    Path::new("data").join(year.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Directories and files that lived directly in `data` before data was kept per year.
+const LEGACY_DATA: &[&str] = &[
+    "inputs",
+    "examples",
+    "puzzles",
+    "answers",
+    "submissions",
+    "timings.json",
+];
+
+/// Moves data of the layout without years, e.g. `data/inputs`, to the active year, e.g. `data/2024/inputs`.
+/// Files that already exist in the year are kept and the legacy file is left in place with a warning, so nothing is
+/// overwritten. Without an active year, legacy data is only reported.
+pub fn migrate_legacy_data() {
+    let legacy: Vec<PathBuf> = LEGACY_DATA
+        .iter()
+        .map(|name| Path::new("data").join(name))
+        .filter(|path| path.exists())
+        .collect();
+
+    if legacy.is_empty() {
+        return;
+    }
+
+    let Some(year) = active() else {
+        eprintln!(
+            "Warning: found data without a year in `data`, set {YEAR_ENV} in .cargo/config.toml to move it to `data/{{year}}`."
+        );
+        return;
+    };
+
+    for from in legacy {
+        let to = data_dir_of(year).join(from.file_name().unwrap_or_default());
+        match move_entry(&from, &to) {
+            Ok(true) => println!("Moved \"{}\" to \"{}\".", from.display(), to.display()),
+            Ok(false) => eprintln!(
+                "Warning: kept \"{}\", some of it already exists in \"{}\". Move it by hand.",
+                from.display(),
+                to.display()
+            ),
+            Err(e) => eprintln!("Warning: failed to move \"{}\": {e}", from.display()),
+        }
+    }
+}
+
+/// Moves a file or the contents of a directory to `to`, without overwriting.
+/// Identical files, e.g. `.keep`, count as moved. Returns `false` if anything was left behind because a different file
+/// already exists at `to`.
+fn move_entry(from: &Path, to: &Path) -> io::Result<bool> {
+    if !from.is_dir() {
+        if to.exists() {
+            if fs::read(from)? != fs::read(to)? {
+                return Ok(false);
+            }
+            fs::remove_file(from)?;
+            return Ok(true);
+        }
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(from, to)?;
+        return Ok(true);
+    }
+
+    fs::create_dir_all(to)?;
+    let mut moved_all = true;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        moved_all &= move_entry(&entry.path(), &to.join(entry.file_name()))?;
+    }
+
+    if moved_all {
+        fs::remove_dir(from)?;
+    }
+
+    Ok(moved_all)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::year_of_bin;
+
+    #[test]
+    fn reads_year_of_archived_bins() {
+        assert_eq!(year_of_bin("2023_01"), Some(2023));
+        assert_eq!(year_of_bin("2023_13_linalg"), Some(2023));
+        assert_eq!(year_of_bin("2023_01-4f9a0c2d1e"), Some(2023));
+    }
+
+    #[test]
+    fn ignores_active_bins() {
+        assert_eq!(year_of_bin("01"), None);
+        assert_eq!(year_of_bin("13_linalg"), None);
+        assert_eq!(year_of_bin("advent_of_code"), None);
+    }
+}